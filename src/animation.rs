@@ -1,12 +1,16 @@
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
+use clap::ValueEnum;
 use globset::{Glob, GlobMatcher};
 use rand::Rng;
+use serde::Serialize;
 use unicode_width::UnicodeWidthStr;
 
 use crate::git::{CommitMetadata, DiffHunk, FileChange, FileStatus, LineChangeType};
-use crate::syntax::Highlighter;
+use crate::syntax::{EditorRules, Highlighter};
+use crate::timing::{DurationTarget, Timing};
 
 /// A rule that specifies typing speed for files matching a glob pattern
 #[derive(Debug, Clone)]
@@ -37,34 +41,54 @@ impl SpeedRule {
 }
 
 // Duration multipliers relative to typing speed
-const CURSOR_MOVE_PAUSE: f64 = 0.5; // Cursor movement between lines (base speed)
 const CURSOR_MOVE_SHORT_MULTIPLIER: f64 = 1.0; // Speed for short distances (1-50 lines)
 const CURSOR_MOVE_MEDIUM_MULTIPLIER: f64 = 0.3; // Speed for medium distances (51-200 lines)
 const CURSOR_MOVE_LONG_MULTIPLIER: f64 = 0.05; // Speed for long distances (201+ lines)
 const MAX_SCROLL_STEPS: usize = 60; // Maximum animation steps for any scroll distance
 const MIN_LOG_STEPS: usize = 50; // Minimum steps for logarithmic scaling (aligned with SHORT threshold)
 const LOG_SCALE_FACTOR: f64 = 8.0; // Scaling factor for logarithmic step calculation
-const DELETE_LINE_PAUSE: f64 = 10.0; // After deleting a line
-const INSERT_LINE_PAUSE: f64 = 6.7; // After inserting a line
-const HUNK_PAUSE: f64 = 50.0; // Between hunks
-const CHECKOUT_PAUSE: f64 = 16.7; // After git checkout command
-const CHECKOUT_OUTPUT_PAUSE: f64 = 33.3; // After git checkout output
-const OPEN_FILE_FIRST_PAUSE: f64 = 33.3; // Before opening first file
-const OPEN_FILE_PAUSE: f64 = 50.0; // Before opening subsequent files
-const OPEN_CMD_PAUSE: f64 = 16.7; // After open command
-const FILE_SWITCH_PAUSE: f64 = 26.7; // After switching file
-const GIT_ADD_PAUSE: f64 = 33.3; // Before git add
-const GIT_ADD_CMD_PAUSE: f64 = 16.7; // After git add command
-const GIT_COMMIT_PAUSE: f64 = 26.7; // After git commit command
-const COMMIT_OUTPUT_PAUSE: f64 = 33.3; // After commit output
-const GIT_PUSH_PAUSE: f64 = 16.7; // After git push command
-const PUSH_OUTPUT_PAUSE: f64 = 10.0; // Between push output lines
-const PUSH_FINAL_PAUSE: f64 = 66.7; // After final push output
+
+/// Format a count with thousands separators (e.g. 1842 -> "1,842")
+fn format_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(ch);
+    }
+    result
+}
+
+/// Where a buffer line comes from, used to look up its syntax highlights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineOrigin {
+    /// Line `n` (0-indexed) of the old content
+    Old(usize),
+    /// Line `n` (0-indexed) of the new content
+    New(usize),
+}
+
+/// How a line was changed while playing the commit, shown in the editor's gutter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineMarker {
+    /// Inserted between existing lines
+    Added,
+    /// Inserted in place of deleted lines
+    Modified,
+}
 
 /// Represents the current state of the editor buffer
 #[derive(Debug, Clone)]
 pub struct EditorBuffer {
     pub lines: Vec<String>,
+    /// Origin of each line in `lines`
+    pub line_origins: Vec<LineOrigin>,
+    /// Marker of each line in `lines`, `None` for lines not inserted yet
+    pub line_markers: Vec<Option<LineMarker>>,
+    /// Deleted lines that the next lines inserted at this position replace, as (line, count)
+    replaced: Option<(usize, usize)>,
     pub cursor_line: usize,
     pub cursor_col: usize,
     pub scroll_offset: usize,
@@ -78,6 +102,8 @@ pub struct EditorBuffer {
     /// Pre-calculated byte offsets for each line (handles CRLF correctly)
     pub old_content_line_offsets: Vec<usize>,
     pub new_content_line_offsets: Vec<usize>,
+    /// The buffer holds only the blank line of an empty file, which the next inserted line replaces
+    placeholder: bool,
 }
 
 impl EditorBuffer {
@@ -85,6 +111,9 @@ impl EditorBuffer {
     pub fn new() -> Self {
         Self {
             lines: vec![String::new()],
+            line_origins: vec![LineOrigin::Old(0)],
+            line_markers: vec![None],
+            replaced: None,
             cursor_line: 0,
             cursor_col: 0,
             scroll_offset: 0,
@@ -95,6 +124,7 @@ impl EditorBuffer {
             new_content_lines: Vec::new(),
             old_content_line_offsets: Vec::new(),
             new_content_line_offsets: Vec::new(),
+            placeholder: true,
         }
     }
 
@@ -107,6 +137,9 @@ impl EditorBuffer {
         };
 
         Self {
+            line_origins: (0..lines.len()).map(LineOrigin::Old).collect(),
+            line_markers: vec![None; lines.len()],
+            replaced: None,
             lines,
             cursor_line: 0,
             cursor_col: 0,
@@ -118,6 +151,7 @@ impl EditorBuffer {
             new_content_lines: Vec::new(),
             old_content_line_offsets: Vec::new(),
             new_content_line_offsets: Vec::new(),
+            placeholder: content.is_empty(),
         }
     }
 
@@ -125,6 +159,8 @@ impl EditorBuffer {
     pub fn insert_char(&mut self, line: usize, col: usize, ch: char) {
         if line >= self.lines.len() {
             self.lines.resize(line + 1, String::new());
+            self.line_origins.resize(line + 1, LineOrigin::New(line));
+            self.line_markers.resize(line + 1, Some(LineMarker::Added));
         }
         let line_str = &mut self.lines[line];
 
@@ -136,29 +172,273 @@ impl EditorBuffer {
             .unwrap_or_else(|| line_str.len());
 
         line_str.insert(byte_idx, ch);
+        self.placeholder = false;
     }
 
     /// Inserts a new line with the given content at the specified position.
-    pub fn insert_line(&mut self, line: usize, content: String) {
+    pub fn insert_line(&mut self, line: usize, content: String, origin: LineOrigin) {
+        let marker = match self.replaced.take() {
+            Some((at, count)) if at == line => {
+                self.replaced = (count > 1).then_some((line + 1, count - 1));
+                LineMarker::Modified
+            }
+            _ => LineMarker::Added,
+        };
+        if std::mem::take(&mut self.placeholder) {
+            self.lines[0] = content;
+            self.line_origins[0] = origin;
+            self.line_markers[0] = Some(marker);
+            return;
+        }
         if line > self.lines.len() {
             self.lines.resize(line, String::new());
+            self.line_origins.resize(line, origin);
+            self.line_markers.resize(line, Some(LineMarker::Added));
         }
         self.lines.insert(line, content);
+        self.line_origins.insert(line, origin);
+        self.line_markers.insert(line, Some(marker));
     }
 
     /// Deletes the line at the specified position.
     pub fn delete_line(&mut self, line: usize) {
         if line < self.lines.len() {
             self.lines.remove(line);
+            self.line_origins.remove(line);
+            self.line_markers.remove(line);
+            self.replaced = match self.replaced {
+                Some((at, count)) if at == line => Some((line, count + 1)),
+                _ => Some((line, 1)),
+            };
         }
         if self.lines.is_empty() {
             self.lines.push(String::new());
+            self.line_origins.push(LineOrigin::New(0));
+            self.line_markers.push(None);
+            self.placeholder = true;
+        }
+    }
+
+    /// Line of the old content to show at the top of a side-by-side view, keeping it
+    /// aligned with the buffer line at `scroll_offset`
+    pub fn old_scroll_offset(&self) -> usize {
+        let top = self.scroll_offset.min(self.line_origins.len());
+        let below =
+            self.line_origins[top..]
+                .iter()
+                .enumerate()
+                .find_map(|(i, origin)| match origin {
+                    LineOrigin::Old(n) => Some(n.saturating_sub(i)),
+                    LineOrigin::New(_) => None,
+                });
+        below.unwrap_or_else(|| {
+            // Only new lines from the top down: continue after the last old line above
+            self.line_origins[..top]
+                .iter()
+                .rev()
+                .find_map(|origin| match origin {
+                    LineOrigin::Old(n) => Some(n + 1),
+                    LineOrigin::New(_) => None,
+                })
+                .unwrap_or(0)
+        })
+    }
+
+    /// Replaces `remove` lines starting at `line` with new lines, the first of which
+    /// is line `new_start` of the new content. Returns the index of the last inserted line.
+    pub fn splice_lines(
+        &mut self,
+        line: usize,
+        remove: usize,
+        lines: Vec<String>,
+        new_start: usize,
+    ) -> usize {
+        let start = line.min(self.lines.len());
+        let remove = if std::mem::take(&mut self.placeholder) {
+            remove.max(1)
+        } else {
+            remove
+        };
+        let end = (start + remove).min(self.lines.len());
+        let inserted = lines.len();
+        self.lines.splice(start..end, lines);
+        self.line_origins.splice(
+            start..end,
+            (new_start..new_start + inserted).map(LineOrigin::New),
+        );
+        // Pasted lines replace the removed ones first, like typed lines after deletions
+        let replaced = end - start;
+        self.line_markers.splice(
+            start..end,
+            (0..inserted).map(|i| {
+                Some(if i < replaced {
+                    LineMarker::Modified
+                } else {
+                    LineMarker::Added
+                })
+            }),
+        );
+        self.replaced = None;
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+            self.line_origins.push(LineOrigin::New(0));
+            self.line_markers.push(None);
+            self.placeholder = true;
+        }
+        (start + inserted.saturating_sub(1)).min(self.lines.len() - 1)
+    }
+}
+
+/// Language-aware editing behavior used when simulating a real editor
+struct SmartEdit {
+    rules: EditorRules,
+    indent_unit: String,
+}
+
+impl SmartEdit {
+    fn new(path: &str, content: &str) -> Self {
+        let language =
+            crate::syntax::get_language(std::path::Path::new(path)).map(|(name, ..)| name);
+        let rules = crate::syntax::get_editor_rules(language);
+        let indent_unit =
+            Self::detect_indent_unit(content).unwrap_or_else(|| rules.indent_unit.to_string());
+        Self { rules, indent_unit }
+    }
+
+    /// Detect the file's indentation unit from the most common indentation increase
+    fn detect_indent_unit(content: &str) -> Option<String> {
+        let mut increments = [0usize; 9];
+        let mut prev_indent = 0;
+
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            if line.starts_with('\t') {
+                return Some("\t".to_string());
+            }
+            let indent = line.chars().take_while(|c| *c == ' ').count();
+            if indent > prev_indent && indent - prev_indent < increments.len() {
+                increments[indent - prev_indent] += 1;
+            }
+            prev_indent = indent;
+        }
+
+        increments
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, &count)| count > 0)
+            .max_by_key(|(width, &count)| (count, std::cmp::Reverse(*width)))
+            .map(|(width, _)| " ".repeat(width))
+    }
+
+    /// Indentation an editor would insert on the line following `prev_line`
+    fn auto_indent(&self, prev_line: &str) -> String {
+        let indent: String = prev_line
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        if prev_line
+            .trim_end()
+            .ends_with(|c| self.rules.indent_after.contains(&c))
+        {
+            indent + &self.indent_unit
+        } else {
+            indent
+        }
+    }
+
+    fn is_closer(&self, ch: char) -> bool {
+        self.rules
+            .pairs
+            .iter()
+            .any(|&(open, close)| open != close && close == ch)
+    }
+
+    /// For each opener in `chars`, the index of its matching closer on the same line.
+    /// Quotes are matched first so brackets inside strings are left alone.
+    fn match_pairs(&self, chars: &[char]) -> Vec<Option<usize>> {
+        let mut matches = vec![None; chars.len()];
+        let mut stack: Vec<(usize, char)> = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let ch = chars[i];
+            if let Some(&(open_idx, close)) = stack.last() {
+                if chars[open_idx] == close {
+                    // Inside a quoted string: only the closing quote matters
+                    if ch == '\\' {
+                        i += 2;
+                        continue;
+                    }
+                    if ch == close {
+                        matches[open_idx] = Some(i);
+                        stack.pop();
+                    }
+                    i += 1;
+                    continue;
+                }
+                if ch == close {
+                    matches[open_idx] = Some(i);
+                    stack.pop();
+                    i += 1;
+                    continue;
+                }
+            }
+            if let Some(&(_, close)) = self.rules.pairs.iter().find(|(open, _)| *open == ch) {
+                stack.push((i, close));
+            }
+            i += 1;
         }
+
+        matches
     }
 }
 
+/// How deleted files are shown in the editor
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum DeletedFileMode {
+    /// Only run `rm` in the terminal
+    #[default]
+    Skip,
+    /// Open the file and clear it in one step
+    SelectAll,
+    /// Open the file and delete it line by line
+    LineByLine,
+}
+
+/// An animation step with its start time and mean duration in milliseconds
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    pub at_ms: u64,
+    pub duration_ms: u64,
+    #[serde(flatten)]
+    pub step: AnimationStep,
+}
+
+/// A file whose animated buffer diverged from its committed content
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferMismatch {
+    pub path: String,
+    /// First differing line (1-indexed)
+    pub line: usize,
+    /// Committed line, or `None` if the buffer has extra lines
+    pub expected: Option<String>,
+    /// Buffer line, or `None` if the buffer is missing lines
+    pub actual: Option<String>,
+}
+
+/// Order in which hunks and files are edited
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum EditOrder {
+    /// Files one after another, hunks top to bottom
+    #[default]
+    Sequential,
+    /// Bounce between files and pick the closest hunk, like real development
+    Organic,
+}
+
 /// Individual animation step
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum AnimationStep {
     InsertChar {
         line: usize,
@@ -168,6 +448,8 @@ pub enum AnimationStep {
     InsertLine {
         line: usize,
         content: String,
+        /// Line number of this line in the new content (0-indexed)
+        new_line: usize,
     },
     DeleteLine {
         line: usize,
@@ -179,6 +461,26 @@ pub enum AnimationStep {
     Pause {
         multiplier: f64,
     },
+    /// Insert an auto-closed bracket or quote pair, leaving the cursor between them
+    InsertPair {
+        line: usize,
+        col: usize,
+        open: char,
+        close: char,
+    },
+    /// Replace a line's content in place (e.g. an editor re-indenting a closing bracket)
+    ReplaceLine {
+        line: usize,
+        content: String,
+    },
+    /// Replace a block of lines in one step (fast-forwarding a large hunk)
+    PasteLines {
+        line: usize,
+        remove: usize,
+        lines: Vec<String>,
+        /// Line number of the first pasted line in the new content (0-indexed)
+        new_start: usize,
+    },
     SwitchFile {
         file_index: usize,
         old_content: String,
@@ -214,6 +516,250 @@ pub enum ActivePane {
     Terminal,
 }
 
+/// Editing progress through one file's hunks, so a file can be left and resumed
+struct FileProgress {
+    applied: Vec<bool>,
+    cursor_line: usize,
+    /// Hunks pasted in one step instead of typed (large files and huge hunks)
+    fast_forward: Vec<bool>,
+    fast_forward_announced: bool,
+    smart_edit: Option<SmartEdit>,
+}
+
+impl FileProgress {
+    fn new(change: &FileChange, smart_edit: bool) -> Self {
+        // Large files animate the first few hunks in full and paste the rest;
+        // huge hunks are pasted regardless
+        let limits = crate::git::large_file_limits();
+        let total_changed_lines: usize = change.hunks.iter().map(changed_line_count).sum();
+        let size = [&change.old_content, &change.new_content]
+            .iter()
+            .filter_map(|content| content.as_ref().map(String::len))
+            .max()
+            .unwrap_or(0);
+        let is_large = total_changed_lines > limits.max_change_lines || size > limits.max_blob_size;
+        let fast_forward = change
+            .hunks
+            .iter()
+            .enumerate()
+            .map(|(i, hunk)| {
+                (is_large && i >= limits.full_hunks)
+                    || changed_line_count(hunk) > limits.max_hunk_lines
+            })
+            .collect();
+
+        Self {
+            applied: vec![false; change.hunks.len()],
+            cursor_line: 0,
+            fast_forward,
+            fast_forward_announced: false,
+            smart_edit: smart_edit.then(|| {
+                SmartEdit::new(
+                    &change.path,
+                    change.new_content.as_deref().unwrap_or_default(),
+                )
+            }),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.applied.iter().all(|&applied| applied)
+    }
+}
+
+/// Number of added and deleted lines in a hunk
+fn changed_line_count(hunk: &DiffHunk) -> usize {
+    hunk.lines
+        .iter()
+        .filter(|l| !matches!(l.change_type, LineChangeType::Context))
+        .count()
+}
+
+/// How many lines a hunk adds to the file (negative if it removes lines)
+fn line_count_delta(hunk: &DiffHunk) -> i64 {
+    hunk.lines
+        .iter()
+        .map(|l| match l.change_type {
+            LineChangeType::Addition => 1,
+            LineChangeType::Deletion => -1,
+            LineChangeType::Context => 0,
+        })
+        .sum()
+}
+
+/// Buffer line where a hunk starts, given which hunks are already applied.
+/// Only applied hunks above it shift its position.
+fn hunk_target_line(hunks: &[DiffHunk], applied: &[bool], index: usize) -> usize {
+    let old_start = hunks[index].old_start;
+    let offset: i64 = hunks
+        .iter()
+        .zip(applied)
+        .filter(|(hunk, &applied)| applied && hunk.old_start < old_start)
+        .map(|(hunk, _)| line_count_delta(hunk))
+        .sum();
+    // hunk.old_start is 1-indexed (Git line numbers start at 1)
+    ((old_start as i64) - 1 + offset).max(0) as usize
+}
+
+// Keywords that introduce a named definition, for pairing definitions with call sites
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "fn",
+    "def",
+    "defn",
+    "function",
+    "func",
+    "fun",
+    "class",
+    "struct",
+    "enum",
+    "trait",
+    "interface",
+    "type",
+    "module",
+    "macro",
+];
+
+/// Split a line into identifier-like words
+fn words(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+}
+
+/// Names defined by a hunk's added lines (e.g. `fn parse_args` defines `parse_args`)
+fn defined_names(hunk: &DiffHunk) -> HashSet<&str> {
+    let mut names = HashSet::new();
+    for line in &hunk.lines {
+        if !matches!(line.change_type, LineChangeType::Addition) {
+            continue;
+        }
+        let mut tokens = words(&line.content);
+        while let Some(token) = tokens.next() {
+            if !DEFINITION_KEYWORDS.contains(&token) {
+                continue;
+            }
+            // Go methods put the receiver before the name: `func (s *Server) Name(`
+            let rest =
+                line.content[line.content.find(token).unwrap_or(0) + token.len()..].trim_start();
+            let name = if rest.starts_with('(') {
+                rest.split_once(')')
+                    .and_then(|(_, after)| words(after).next())
+            } else {
+                tokens.next()
+            };
+            if let Some(name) = name.filter(|n| n.len() >= 3) {
+                names.insert(name);
+            }
+            break;
+        }
+    }
+    names
+}
+
+/// Names used by a hunk's added lines
+fn used_names(hunk: &DiffHunk) -> HashSet<&str> {
+    hunk.lines
+        .iter()
+        .filter(|l| matches!(l.change_type, LineChangeType::Addition))
+        .flat_map(|l| words(&l.content))
+        .collect()
+}
+
+/// Plan an "organic" editing order across files: within a file, the next hunk is the
+/// one closest to the cursor (so hunks above are edited bottom-up when that is shorter),
+/// and after a hunk that defines a function or type, editing jumps to a hunk in another
+/// file that uses it before coming back. Returns `(file_index, hunk indices)` segments.
+fn plan_organic_edits(changes: &[FileChange], files: &[usize]) -> Vec<(usize, Vec<usize>)> {
+    let hunks = |f: usize| &changes[files[f]].hunks;
+    let defines: Vec<Vec<HashSet<&str>>> = (0..files.len())
+        .map(|f| hunks(f).iter().map(defined_names).collect())
+        .collect();
+    let uses: Vec<Vec<HashSet<&str>>> = (0..files.len())
+        .map(|f| hunks(f).iter().map(used_names).collect())
+        .collect();
+
+    let mut applied: Vec<Vec<bool>> = (0..files.len())
+        .map(|f| vec![false; hunks(f).len()])
+        .collect();
+    let mut cursor = vec![0usize; files.len()];
+    let mut visited = vec![false; files.len()];
+    let mut plan: Vec<(usize, Vec<usize>)> = Vec::new();
+
+    let apply = |f: usize,
+                 h: usize,
+                 applied: &mut Vec<Vec<bool>>,
+                 cursor: &mut Vec<usize>,
+                 plan: &mut Vec<(usize, Vec<usize>)>| {
+        let hunk = &hunks(f)[h];
+        let target = hunk_target_line(hunks(f), &applied[f], h);
+        let new_len = hunk
+            .lines
+            .iter()
+            .filter(|l| !matches!(l.change_type, LineChangeType::Deletion))
+            .count();
+        cursor[f] = target + new_len.saturating_sub(1);
+        applied[f][h] = true;
+        match plan.last_mut() {
+            Some((file, order)) if *file == files[f] => order.push(h),
+            _ => plan.push((files[f], vec![h])),
+        }
+    };
+    let pending = |f: usize, applied: &Vec<Vec<bool>>| applied[f].iter().any(|&a| !a);
+
+    let mut current = 0;
+    loop {
+        if visited.get(current) == Some(&true) && !pending(current, &applied) {
+            match (0..files.len()).find(|&f| !visited[f] || pending(f, &applied)) {
+                Some(f) => current = f,
+                None => break,
+            }
+        }
+        if current >= files.len() {
+            break;
+        }
+        visited[current] = true;
+
+        // Files without hunks (e.g. empty new files) are still opened
+        if hunks(current).is_empty() {
+            plan.push((files[current], Vec::new()));
+            continue;
+        }
+
+        // Next hunk: the closest one to the cursor
+        let Some(h) = (0..hunks(current).len())
+            .filter(|&h| !applied[current][h])
+            .min_by_key(|&h| {
+                hunk_target_line(hunks(current), &applied[current], h).abs_diff(cursor[current])
+            })
+        else {
+            continue;
+        };
+        apply(current, h, &mut applied, &mut cursor, &mut plan);
+
+        // Jump to a call site of whatever this hunk defined, then come back
+        if defines[current][h].is_empty() {
+            continue;
+        }
+        let call_site = (0..files.len())
+            .filter(|&f| f != current)
+            .flat_map(|f| (0..hunks(f).len()).map(move |h| (f, h)))
+            .find(|&(f, h2)| {
+                !applied[f][h2]
+                    && uses[f][h2]
+                        .iter()
+                        .any(|name| defines[current][h].contains(name))
+            });
+        if let Some((f, h2)) = call_site {
+            visited[f] = true;
+            apply(f, h2, &mut applied, &mut cursor, &mut plan);
+            if !pending(current, &applied) {
+                current = f;
+            }
+        }
+    }
+
+    plan
+}
+
 /// Main animation engine
 pub struct AnimationEngine {
     pub buffer: EditorBuffer,
@@ -225,6 +771,8 @@ pub struct AnimationEngine {
     base_speed_ms: u64,
     next_step_delay: u64,
     pause_until: Option<Instant>,
+    /// When the viewer paused playback, if paused
+    paused_at: Option<Instant>,
     pub cursor_visible: bool,
     cursor_blink_timer: Instant,
     viewport_height: usize,
@@ -232,10 +780,10 @@ pub struct AnimationEngine {
     pub current_file_index: usize,
     pub current_file_path: Option<String>,
     pub terminal_lines: Vec<String>,
+    /// Terminal lines from before the current commit, restored when it starts over
+    commit_start_terminal_lines: Vec<String>,
     pub active_pane: ActivePane,
     pub highlighter: RefCell<Highlighter>,
-    /// Track cumulative line offset from old_content (insertions - deletions)
-    pub line_offset: isize,
     /// Target frames per second for rendering
     #[allow(dead_code)]
     target_fps: u64,
@@ -253,6 +801,22 @@ pub struct AnimationEngine {
     pending_metadata: Option<CommitMetadata>,
     /// Speed rules for different file patterns
     speed_rules: Vec<SpeedRule>,
+    /// Simulate editor auto-pairing and auto-indentation while typing
+    smart_edit: bool,
+    /// Pause durations for each animation phase
+    timing: Timing,
+    /// How deleted files are shown in the editor
+    deleted_file_mode: DeletedFileMode,
+    /// Animate the content diff of renamed files with edits
+    animate_renames: bool,
+    /// Order in which hunks and files are edited
+    edit_order: EditOrder,
+    /// Editor state of files left mid-edit, restored when switching back to them
+    saved_buffers: HashMap<usize, EditorBuffer>,
+    /// Target wall-clock duration for each commit
+    duration_target: DurationTarget,
+    /// Multiplier applied to typing speed and pauses to meet the duration target
+    speed_scale: f64,
 }
 
 impl AnimationEngine {
@@ -271,6 +835,7 @@ impl AnimationEngine {
             base_speed_ms: speed_ms,
             next_step_delay: speed_ms,
             pause_until: None,
+            paused_at: None,
             cursor_visible: true,
             cursor_blink_timer: now,
             viewport_height: 20, // Default, will be updated from UI
@@ -278,9 +843,9 @@ impl AnimationEngine {
             current_file_index: 0,
             current_file_path: None,
             terminal_lines: Vec::new(),
+            commit_start_terminal_lines: Vec::new(),
             active_pane: ActivePane::Terminal, // Start with terminal (git checkout)
             highlighter: RefCell::new(Highlighter::new()),
-            line_offset: 0,
             target_fps,
             frame_interval_ms,
             last_frame: now,
@@ -289,6 +854,14 @@ impl AnimationEngine {
             current_metadata: None,
             pending_metadata: None,
             speed_rules: Vec::new(),
+            smart_edit: false,
+            timing: Timing::default(),
+            deleted_file_mode: DeletedFileMode::default(),
+            animate_renames: false,
+            edit_order: EditOrder::default(),
+            saved_buffers: HashMap::new(),
+            duration_target: DurationTarget::default(),
+            speed_scale: 1.0,
         }
     }
 
@@ -297,6 +870,35 @@ impl AnimationEngine {
         self.speed_rules = rules;
     }
 
+    /// Enable or disable editor auto-pair and auto-indent simulation
+    pub fn set_smart_edit(&mut self, enabled: bool) {
+        self.smart_edit = enabled;
+    }
+
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    pub fn timing(&self) -> &Timing {
+        &self.timing
+    }
+
+    pub fn set_duration_target(&mut self, target: DurationTarget) {
+        self.duration_target = target;
+    }
+
+    pub fn set_deleted_file_mode(&mut self, mode: DeletedFileMode) {
+        self.deleted_file_mode = mode;
+    }
+
+    pub fn set_animate_renames(&mut self, enabled: bool) {
+        self.animate_renames = enabled;
+    }
+
+    pub fn set_edit_order(&mut self, order: EditOrder) {
+        self.edit_order = order;
+    }
+
     /// Get the speed for a given file path based on speed rules
     /// Returns the first matching rule's speed, or the base speed if no match
     fn get_speed_for_file(&self, path: &str) -> u64 {
@@ -323,6 +925,23 @@ impl AnimationEngine {
         self.current_metadata.as_ref()
     }
 
+    /// Lines (0-indexed) of the current file's old content that the change deletes
+    pub fn deleted_old_lines(&self) -> HashSet<usize> {
+        self.current_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.changes.get(self.current_file_index))
+            .map(|change| {
+                change
+                    .hunks
+                    .iter()
+                    .flat_map(|hunk| &hunk.lines)
+                    .filter(|l| l.change_type == LineChangeType::Deletion)
+                    .filter_map(|l| l.old_line_no.map(|n| n.saturating_sub(1)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     fn calculate_line_offsets(content: &str) -> Vec<usize> {
         std::iter::once(0)
             .chain(content.bytes().enumerate().filter_map(|(i, b)| {
@@ -347,9 +966,13 @@ impl AnimationEngine {
     pub fn load_commit(&mut self, metadata: &CommitMetadata) {
         // Store pending metadata to be applied on ResetState
         self.pending_metadata = Some(metadata.clone());
+        self.commit_start_terminal_lines = self.terminal_lines.clone();
 
         self.steps.clear();
+        self.saved_buffers.clear();
         self.current_step = 0;
+        // Steps before the first file run at the base speed, not the last file's
+        self.speed_ms = self.base_speed_ms;
         self.state = AnimationState::Playing;
         self.last_update = Instant::now();
         self.pause_until = None;
@@ -361,7 +984,7 @@ impl AnimationEngine {
             // Simplified intro for working tree diffs
             self.add_terminal_command("git diff --stat");
             self.steps.push(AnimationStep::Pause {
-                multiplier: CHECKOUT_PAUSE,
+                multiplier: self.timing.checkout,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!("📝 {}", metadata.message),
@@ -374,26 +997,26 @@ impl AnimationEngine {
                 ),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: CHECKOUT_OUTPUT_PAUSE,
+                multiplier: self.timing.checkout_output,
             });
         } else {
             // Time travel to commit date
             let datetime_str = metadata.date.format("%Y-%m-%d %H:%M:%S").to_string();
             self.add_terminal_command(&format!("time-travel {}", datetime_str));
             self.steps.push(AnimationStep::Pause {
-                multiplier: CHECKOUT_PAUSE,
+                multiplier: self.timing.checkout,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "⚡ Initializing temporal displacement field...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: CHECKOUT_OUTPUT_PAUSE * 0.5,
+                multiplier: self.timing.checkout_output * 0.5,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "✨ Warping through spacetime...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: CHECKOUT_OUTPUT_PAUSE * 0.5,
+                multiplier: self.timing.checkout_output * 0.5,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!("🕰️  Arrived at {}", datetime_str),
@@ -406,7 +1029,7 @@ impl AnimationEngine {
                 ),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: CHECKOUT_OUTPUT_PAUSE,
+                multiplier: self.timing.checkout_output,
             });
         }
 
@@ -417,6 +1040,7 @@ impl AnimationEngine {
         let sorted_indices = metadata.sorted_file_indices();
 
         // Process all file changes in sorted order
+        let mut organic_done = false;
         for &index in &sorted_indices {
             let change = &metadata.changes[index];
             match (change.is_excluded, &change.status) {
@@ -433,7 +1057,7 @@ impl AnimationEngine {
                     });
 
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: OPEN_FILE_PAUSE,
+                        multiplier: self.timing.open_file,
                     });
                     let reason = change
                         .exclusion_reason
@@ -443,109 +1067,114 @@ impl AnimationEngine {
                         text: format!("📦 {} (skipped - {})", change.path, reason),
                     });
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: OPEN_CMD_PAUSE,
+                        multiplier: self.timing.open_cmd,
                     });
                 }
-                // For deleted files, skip editor animation and only run rm + git add
+                // For deleted files, optionally clear the file in the editor, then run rm + git add
                 (false, FileStatus::Deleted) => {
-                    // Switch to the deleted file to show in file tree
                     let old_content = change.old_content.clone().unwrap_or_default();
-                    self.steps.push(AnimationStep::SwitchFile {
-                        file_index: index,
-                        old_content,
-                        new_content: String::new(),
-                        path: change.path.clone(),
-                    });
+                    if self.deleted_file_mode == DeletedFileMode::Skip {
+                        // Switch to the deleted file to show in file tree
+                        self.steps.push(AnimationStep::SwitchFile {
+                            file_index: index,
+                            old_content,
+                            new_content: String::new(),
+                            path: change.path.clone(),
+                        });
+                    } else {
+                        self.add_open_file_steps(
+                            index,
+                            &change.path,
+                            old_content.clone(),
+                            String::new(),
+                        );
+                        self.generate_steps_for_deletion(&old_content);
+                    }
 
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_PAUSE,
+                        multiplier: self.timing.git_add,
                     });
                     self.add_terminal_command(&format!("rm {}", change.path));
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_CMD_PAUSE,
+                        multiplier: self.timing.git_add_cmd,
                     });
                     self.add_terminal_command(&format!("git add {}", change.path));
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_CMD_PAUSE,
+                        multiplier: self.timing.git_add_cmd,
                     });
                 }
-                // For renamed/moved files, skip editor animation and only run mv + git add
+                // For renamed/moved files, run mv, optionally animate the edits, then git add
                 (false, FileStatus::Renamed) => {
-                    // Switch to the renamed file to show in file tree
                     let old_content = change.old_content.clone().unwrap_or_default();
                     let new_content = change.new_content.clone().unwrap_or_default();
-                    self.steps.push(AnimationStep::SwitchFile {
-                        file_index: index,
-                        old_content,
-                        new_content,
-                        path: change.path.clone(),
-                    });
+                    let animate_edits = self.animate_renames && !change.hunks.is_empty();
+
+                    if !animate_edits {
+                        // Switch to the renamed file to show in file tree
+                        self.steps.push(AnimationStep::SwitchFile {
+                            file_index: index,
+                            old_content: old_content.clone(),
+                            new_content: new_content.clone(),
+                            path: change.path.clone(),
+                        });
+                    }
 
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_PAUSE,
+                        multiplier: self.timing.git_add,
                     });
                     if let Some(old_path) = &change.old_path {
                         self.add_terminal_command(&format!("mv {} {}", old_path, change.path));
                         self.steps.push(AnimationStep::Pause {
-                            multiplier: GIT_ADD_CMD_PAUSE,
+                            multiplier: self.timing.git_add_cmd,
                         });
                     }
-                    self.add_terminal_command(&format!("git add {}", change.path));
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_CMD_PAUSE,
-                    });
-                }
-                // Normal files (Added, Modified, etc.) - full editor animation
-                (false, _) => {
-                    // Open file in editor
-                    if index == 0 {
-                        self.steps.push(AnimationStep::Pause {
-                            multiplier: OPEN_FILE_FIRST_PAUSE,
-                        });
-                    } else {
+
+                    if animate_edits {
+                        // Similarity < 100%: edit the moved file before staging it
+                        self.add_open_file_steps(index, &change.path, old_content, new_content);
+                        self.generate_steps_for_file(change);
                         self.steps.push(AnimationStep::Pause {
-                            multiplier: OPEN_FILE_PAUSE,
+                            multiplier: self.timing.git_add,
                         });
                     }
-                    // Show "Open File..." dialog and type the file path
-                    self.steps.push(AnimationStep::OpenFileDialogStart);
-                    self.steps.push(AnimationStep::Pause { multiplier: 5.0 });
-
-                    // Type each character of the file path
-                    for ch in change.path.chars() {
-                        self.steps.push(AnimationStep::DialogTypeChar { ch });
-                    }
 
+                    self.add_terminal_command(&format!("git add {}", change.path));
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: OPEN_CMD_PAUSE,
+                        multiplier: self.timing.git_add_cmd,
                     });
-
-                    // Add file switch step with both old and new content
+                }
+                // Organic mode edits all normal files together, bouncing between them
+                (false, _) if self.edit_order == EditOrder::Organic => {
+                    if !organic_done {
+                        let files: Vec<usize> = sorted_indices
+                            .iter()
+                            .copied()
+                            .filter(|&i| {
+                                let change = &metadata.changes[i];
+                                !change.is_excluded
+                                    && !matches!(
+                                        change.status,
+                                        FileStatus::Deleted | FileStatus::Renamed
+                                    )
+                            })
+                            .collect();
+                        let plan = plan_organic_edits(&metadata.changes, &files);
+                        self.generate_organic_edits(&metadata.changes, &plan);
+                        organic_done = true;
+                    }
+                }
+                // Normal files (Added, Modified, etc.) - full editor animation
+                (false, _) => {
+                    // Open file in editor with both old and new content
                     let old_content = change.old_content.clone().unwrap_or_default();
                     let new_content = change.new_content.clone().unwrap_or_default();
-                    self.steps.push(AnimationStep::SwitchFile {
-                        file_index: index,
-                        old_content,
-                        new_content,
-                        path: change.path.clone(),
-                    });
-
-                    // Add pause before starting file animation
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: FILE_SWITCH_PAUSE,
-                    });
+                    self.add_open_file_steps(index, &change.path, old_content, new_content);
 
                     // Generate animation steps for this file
                     self.generate_steps_for_file(change);
 
                     // Git add this file after editing
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_PAUSE,
-                    });
-                    self.add_terminal_command(&format!("git add {}", change.path));
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_CMD_PAUSE,
-                    });
+                    self.add_git_add_steps(&change.path);
                 }
             }
         }
@@ -554,7 +1183,7 @@ impl AnimationEngine {
         if is_working_tree {
             // Just add a final pause for working tree mode
             self.steps.push(AnimationStep::Pause {
-                multiplier: PUSH_FINAL_PAUSE,
+                multiplier: self.timing.push_final,
             });
         } else {
             // Git commit
@@ -562,7 +1191,7 @@ impl AnimationEngine {
             let commit_message = metadata.message.lines().next().unwrap_or("Update");
             self.add_terminal_command(&format!("git commit -m \"{}\"", commit_message));
             self.steps.push(AnimationStep::Pause {
-                multiplier: GIT_COMMIT_PAUSE,
+                multiplier: self.timing.git_commit,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!("💾 [main {}] {}", &metadata.hash[..7], commit_message),
@@ -575,37 +1204,37 @@ impl AnimationEngine {
                 ),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: COMMIT_OUTPUT_PAUSE,
+                multiplier: self.timing.commit_output,
             });
 
             // Git push
             self.add_terminal_command("git push origin main");
             self.steps.push(AnimationStep::Pause {
-                multiplier: GIT_PUSH_PAUSE,
+                multiplier: self.timing.git_push,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "🚀 Launching code into the cloud...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: PUSH_OUTPUT_PAUSE,
+                multiplier: self.timing.push_output,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "📦 Compressing digital dreams: 100% (5/5)".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: PUSH_OUTPUT_PAUSE,
+                multiplier: self.timing.push_output,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "✍️  Signing with invisible ink: done.".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: GIT_PUSH_PAUSE,
+                multiplier: self.timing.git_push,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "📡 Beaming to origin/main via satellite...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: PUSH_OUTPUT_PAUSE,
+                multiplier: self.timing.push_output,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!(
@@ -615,19 +1244,254 @@ impl AnimationEngine {
                 ),
             });
             self.steps.push(AnimationStep::Pause {
-                multiplier: PUSH_FINAL_PAUSE,
+                multiplier: self.timing.push_final,
             });
         }
 
         // Start with empty editor (no file opened yet)
         self.buffer = EditorBuffer::new();
+
+        // Stretch or compress the whole commit to fit the duration target
+        self.speed_scale = 1.0;
+        self.speed_scale = self
+            .duration_target
+            .speed_scale(self.estimate_duration_ms() as f64);
+    }
+
+    /// Estimate how long the generated steps take to play, using the mean typing delay
+    pub fn estimate_duration_ms(&self) -> u64 {
+        self.step_durations_ms().iter().sum::<f64>() as u64
+    }
+
+    /// Mean time spent on each generated step, following speed rules and pause multipliers
+    fn step_durations_ms(&self) -> Vec<f64> {
+        let mut speed_ms = self.base_speed_ms;
+        self.steps
+            .iter()
+            .map(|step| {
+                let speed = speed_ms as f64 * self.speed_scale;
+                match step {
+                    AnimationStep::DialogTypeChar { .. } => speed * 2.0,
+                    AnimationStep::Pause { multiplier } => speed * multiplier,
+                    AnimationStep::SwitchFile { path, .. } => {
+                        speed_ms = self.get_speed_for_file(path);
+                        speed
+                    }
+                    _ => speed,
+                }
+            })
+            .collect()
+    }
+
+    /// The generated steps with cumulative start times, for replaying them in other tools
+    pub fn timeline(&self) -> Vec<TimelineEntry> {
+        let mut at_ms = 0.0;
+        self.steps
+            .iter()
+            .zip(self.step_durations_ms())
+            .map(|(step, duration_ms)| {
+                let entry = TimelineEntry {
+                    at_ms: at_ms as u64,
+                    duration_ms: duration_ms as u64,
+                    step: step.clone(),
+                };
+                at_ms += duration_ms;
+                entry
+            })
+            .collect()
+    }
+
+    /// Executes the next step immediately, ignoring its delay, for offline rendering.
+    /// Returns `false` once every step has run.
+    pub fn run_next_step(&mut self) -> bool {
+        if self.current_step >= self.steps.len() {
+            self.state = AnimationState::Finished;
+            return false;
+        }
+        let step = self.steps[self.current_step].clone();
+        self.execute_step(step);
+        self.current_step += 1;
+        self.pause_until = None;
+        true
+    }
+
+    /// Load a commit and run all of its steps instantly, without pauses or rendering,
+    /// then report every animated file whose final buffer differs from its committed content
+    pub fn verify_commit(&mut self, metadata: &CommitMetadata) -> Vec<BufferMismatch> {
+        self.load_commit(metadata);
+        while self.run_next_step() {}
+
+        let mut buffers = self.saved_buffers.clone();
+        if self.current_file_path.is_some() {
+            buffers.insert(self.current_file_index, self.buffer.clone());
+        }
+
+        let mut mismatches = Vec::new();
+        for (index, change) in metadata.changes.iter().enumerate() {
+            let Some(buffer) = buffers.get(&index) else {
+                continue;
+            };
+            let expected = match change.status {
+                _ if change.is_excluded || change.is_binary => continue,
+                FileStatus::Deleted if self.deleted_file_mode == DeletedFileMode::Skip => continue,
+                FileStatus::Deleted => "",
+                FileStatus::Renamed if !self.animate_renames || change.hunks.is_empty() => continue,
+                _ => change.new_content.as_deref().unwrap_or_default(),
+            };
+            let expected = EditorBuffer::from_content(expected).lines;
+            if buffer.lines == expected {
+                continue;
+            }
+            let line = buffer
+                .lines
+                .iter()
+                .zip(&expected)
+                .take_while(|(actual, expected)| actual == expected)
+                .count();
+            mismatches.push(BufferMismatch {
+                path: change.path.clone(),
+                line: line + 1,
+                expected: expected.get(line).cloned(),
+                actual: buffer.lines.get(line).cloned(),
+            });
+        }
+        mismatches
+    }
+
+    /// Typing speed for the current file, scaled to meet the duration target
+    fn effective_speed_ms(&self) -> f64 {
+        self.speed_ms as f64 * self.speed_scale
+    }
+
+    /// Open a file in the editor through the "Open File..." dialog
+    fn add_open_file_steps(
+        &mut self,
+        file_index: usize,
+        path: &str,
+        old_content: String,
+        new_content: String,
+    ) {
+        if file_index == 0 {
+            self.steps.push(AnimationStep::Pause {
+                multiplier: self.timing.open_file_first,
+            });
+        } else {
+            self.steps.push(AnimationStep::Pause {
+                multiplier: self.timing.open_file,
+            });
+        }
+        // Show "Open File..." dialog and type the file path
+        self.steps.push(AnimationStep::OpenFileDialogStart);
+        self.steps.push(AnimationStep::Pause {
+            multiplier: self.timing.open_dialog,
+        });
+
+        // Type each character of the file path
+        for ch in path.chars() {
+            self.steps.push(AnimationStep::DialogTypeChar { ch });
+        }
+
+        self.steps.push(AnimationStep::Pause {
+            multiplier: self.timing.open_cmd,
+        });
+
+        self.steps.push(AnimationStep::SwitchFile {
+            file_index,
+            old_content,
+            new_content,
+            path: path.to_string(),
+        });
+
+        // Add pause before starting file animation
+        self.steps.push(AnimationStep::Pause {
+            multiplier: self.timing.file_switch,
+        });
+    }
+
+    /// Generate animation steps that empty a deleted file in the editor
+    fn generate_steps_for_deletion(&mut self, old_content: &str) {
+        let line_count = old_content.lines().count();
+        if line_count == 0 {
+            return;
+        }
+
+        // Very long files are always cleared with select-all, like large hunks are pasted
+        let line_by_line = self.deleted_file_mode == DeletedFileMode::LineByLine
+            && line_count <= crate::git::large_file_limits().max_hunk_lines;
+
+        if line_by_line {
+            for _ in 0..line_count {
+                self.steps.push(AnimationStep::DeleteLine { line: 0 });
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: self.timing.delete_line,
+                });
+            }
+        } else {
+            self.steps
+                .push(AnimationStep::MoveCursor { line: 0, col: 0 });
+            self.steps.push(AnimationStep::Pause {
+                multiplier: self.timing.delete_line,
+            });
+            self.steps.push(AnimationStep::PasteLines {
+                line: 0,
+                remove: line_count,
+                lines: Vec::new(),
+                new_start: 0,
+            });
+            self.steps.push(AnimationStep::Pause {
+                multiplier: self.timing.delete_line,
+            });
+        }
+    }
+
+    /// Stage a file after editing it
+    fn add_git_add_steps(&mut self, path: &str) {
+        self.steps.push(AnimationStep::Pause {
+            multiplier: self.timing.git_add,
+        });
+        self.add_terminal_command(&format!("git add {}", path));
+        self.steps.push(AnimationStep::Pause {
+            multiplier: self.timing.git_add_cmd,
+        });
+    }
+
+    /// Generate animation steps for an organic edit plan, reopening files as the
+    /// plan bounces between them and staging each file once it is complete
+    fn generate_organic_edits(&mut self, changes: &[FileChange], plan: &[(usize, Vec<usize>)]) {
+        let mut progress: HashMap<usize, FileProgress> = HashMap::new();
+
+        for (file_index, hunk_order) in plan {
+            let change = &changes[*file_index];
+            let old_content = change.old_content.clone().unwrap_or_default();
+            let new_content = change.new_content.clone().unwrap_or_default();
+            self.add_open_file_steps(*file_index, &change.path, old_content, new_content);
+
+            let smart_edit = self.smart_edit;
+            let file_progress = progress
+                .entry(*file_index)
+                .or_insert_with(|| FileProgress::new(change, smart_edit));
+            self.generate_steps_for_hunks(change, file_progress, hunk_order);
+
+            if file_progress.is_done() {
+                self.add_git_add_steps(&change.path);
+            }
+        }
     }
 
     /// Generate animation steps for a file change
     fn generate_steps_for_file(&mut self, change: &FileChange) {
-        let mut current_cursor_line = 0;
-        let mut line_offset = 0i64; // Track how buffer lines differ from old file
+        let mut progress = FileProgress::new(change, self.smart_edit);
+        let order: Vec<usize> = (0..change.hunks.len()).collect();
+        self.generate_steps_for_hunks(change, &mut progress, &order);
+    }
 
+    /// Generate animation steps for some of a file's hunks, in the given order
+    fn generate_steps_for_hunks(
+        &mut self,
+        change: &FileChange,
+        progress: &mut FileProgress,
+        order: &[usize],
+    ) {
         // Parse old_content into lines for indentation calculation during cursor movement
         let old_lines: Vec<&str> = change
             .old_content
@@ -635,50 +1499,89 @@ impl AnimationEngine {
             .map(|c| c.lines().collect())
             .unwrap_or_default();
 
-        // Process each hunk
-        for hunk in &change.hunks {
-            // Calculate target line in current buffer
-            // hunk.old_start is 1-indexed (Git line numbers start at 1)
-            // We need to convert to 0-indexed and adjust by how many lines we've added/removed
-            let target_line = ((hunk.old_start as i64) - 1 + line_offset).max(0) as usize;
-
-            // Calculate distance for speed adjustment
-            let distance = target_line.abs_diff(current_cursor_line);
-
-            current_cursor_line = self.generate_cursor_movement(
-                current_cursor_line,
-                target_line,
-                distance,
-                &old_lines,
-            );
-
-            let (final_cursor_line, _final_buffer_line) =
-                self.generate_steps_for_hunk(hunk, current_cursor_line, target_line);
-
-            current_cursor_line = final_cursor_line;
-
-            // Update offset based on changes in this hunk
-            // Count additions and deletions to update the offset
-            let additions = hunk
-                .lines
-                .iter()
-                .filter(|l| matches!(l.change_type, LineChangeType::Addition))
-                .count() as i64;
-            let deletions = hunk
-                .lines
-                .iter()
-                .filter(|l| matches!(l.change_type, LineChangeType::Deletion))
-                .count() as i64;
+        for &hunk_index in order {
+            let hunk = &change.hunks[hunk_index];
+            // Calculate target line in current buffer, adjusted by the hunks already applied above
+            let target_line = hunk_target_line(&change.hunks, &progress.applied, hunk_index);
+
+            if progress.fast_forward[hunk_index] {
+                if !progress.fast_forward_announced {
+                    let skipped_lines: usize = change
+                        .hunks
+                        .iter()
+                        .zip(&progress.fast_forward)
+                        .filter(|(_, &ff)| ff)
+                        .map(|(hunk, _)| changed_line_count(hunk))
+                        .sum();
+                    self.steps.push(AnimationStep::TerminalOutput {
+                        text: format!(
+                            "⏩ fast-forwarding {} lines",
+                            format_thousands(skipped_lines)
+                        ),
+                    });
+                    progress.fast_forward_announced = true;
+                }
+                progress.cursor_line = self.generate_paste_for_hunk(hunk, target_line);
+            } else {
+                // Calculate distance for speed adjustment
+                let distance = target_line.abs_diff(progress.cursor_line);
+
+                let cursor_line = self.generate_cursor_movement(
+                    progress.cursor_line,
+                    target_line,
+                    distance,
+                    &old_lines,
+                );
+
+                let (final_cursor_line, _final_buffer_line) = self.generate_steps_for_hunk(
+                    hunk,
+                    cursor_line,
+                    target_line,
+                    progress.smart_edit.as_ref(),
+                );
+
+                progress.cursor_line = final_cursor_line;
+            }
 
-            line_offset += additions - deletions;
+            progress.applied[hunk_index] = true;
 
             // Add pause between hunks
             self.steps.push(AnimationStep::Pause {
-                multiplier: HUNK_PAUSE,
+                multiplier: self.timing.hunk,
             });
         }
     }
 
+    /// Jump straight to a hunk and paste its new lines in one step
+    /// Returns the final cursor line
+    fn generate_paste_for_hunk(&mut self, hunk: &DiffHunk, target_line: usize) -> usize {
+        let remove = hunk
+            .lines
+            .iter()
+            .filter(|l| !matches!(l.change_type, LineChangeType::Addition))
+            .count();
+        let lines: Vec<String> = hunk
+            .lines
+            .iter()
+            .filter(|l| !matches!(l.change_type, LineChangeType::Deletion))
+            .map(|l| l.content.clone())
+            .collect();
+        let final_line = target_line + lines.len().saturating_sub(1);
+
+        self.steps.push(AnimationStep::MoveCursor {
+            line: target_line,
+            col: 0,
+        });
+        self.steps.push(AnimationStep::PasteLines {
+            line: target_line,
+            remove,
+            lines,
+            new_start: hunk.new_start.saturating_sub(1),
+        });
+
+        final_line
+    }
+
     /// Generate cursor movement steps from current line to target line
     fn generate_cursor_movement(
         &mut self,
@@ -732,7 +1635,7 @@ impl AnimationEngine {
         }
 
         // Generate movement steps
-        let pause_multiplier = (CURSOR_MOVE_PAUSE * base_speed_multiplier).max(0.01);
+        let pause_multiplier = (self.timing.cursor_move * base_speed_multiplier).max(0.01);
 
         for line in positions {
             if line != from_line {
@@ -768,10 +1671,15 @@ impl AnimationEngine {
         hunk: &DiffHunk,
         start_cursor_line: usize,
         start_buffer_line: usize,
+        smart_edit: Option<&SmartEdit>,
     ) -> (usize, usize) {
         // buffer_line tracks the actual line number in the current buffer
         let mut buffer_line = start_buffer_line;
         let mut cursor_line = start_cursor_line;
+        // new_line tracks the line number in the new content (0-indexed)
+        let mut new_line = hunk.new_start.saturating_sub(1);
+        // Last non-blank line above the cursor, used for auto-indentation
+        let mut prev_line: Option<&str> = None;
 
         for line_change in &hunk.lines {
             match line_change.change_type {
@@ -780,7 +1688,7 @@ impl AnimationEngine {
                     self.steps
                         .push(AnimationStep::DeleteLine { line: buffer_line });
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: DELETE_LINE_PAUSE,
+                        multiplier: self.timing.delete_line,
                     });
                     cursor_line = buffer_line;
                     // After deletion, buffer_line stays the same
@@ -788,29 +1696,47 @@ impl AnimationEngine {
                 }
                 LineChangeType::Addition => {
                     let content = &line_change.content;
-                    let indentation_len = content.chars().take_while(|c| c.is_whitespace()).count();
 
-                    // Insert line with indentation already included
-                    let indentation: String = content.chars().take(indentation_len).collect();
-                    self.steps.push(AnimationStep::InsertLine {
-                        line: buffer_line,
-                        content: indentation,
-                    });
+                    if let Some(smart_edit) = smart_edit {
+                        self.generate_smart_addition(
+                            smart_edit,
+                            buffer_line,
+                            new_line,
+                            content,
+                            prev_line,
+                        );
+                    } else {
+                        let indentation_len =
+                            content.chars().take_while(|c| c.is_whitespace()).count();
 
-                    // Type each character after the indentation
-                    for (i, ch) in content.chars().skip(indentation_len).enumerate() {
-                        self.steps.push(AnimationStep::InsertChar {
+                        // Insert line with indentation already included
+                        let indentation: String = content.chars().take(indentation_len).collect();
+                        self.steps.push(AnimationStep::InsertLine {
                             line: buffer_line,
-                            col: indentation_len + i,
-                            ch,
+                            content: indentation,
+                            new_line,
                         });
+
+                        // Type each character after the indentation
+                        for (i, ch) in content.chars().skip(indentation_len).enumerate() {
+                            self.steps.push(AnimationStep::InsertChar {
+                                line: buffer_line,
+                                col: indentation_len + i,
+                                ch,
+                            });
+                        }
+                    }
+
+                    if !content.trim().is_empty() {
+                        prev_line = Some(content);
                     }
 
                     cursor_line = buffer_line;
                     buffer_line += 1; // Move to next line after insertion
+                    new_line += 1;
 
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: INSERT_LINE_PAUSE,
+                        multiplier: self.timing.insert_line,
                     });
                 }
                 LineChangeType::Context => {
@@ -827,11 +1753,15 @@ impl AnimationEngine {
                             col,
                         });
                         self.steps.push(AnimationStep::Pause {
-                            multiplier: CURSOR_MOVE_PAUSE,
+                            multiplier: self.timing.cursor_move,
                         });
                     }
+                    if !line_change.content.trim().is_empty() {
+                        prev_line = Some(&line_change.content);
+                    }
                     cursor_line = buffer_line;
                     buffer_line += 1; // Move to next line
+                    new_line += 1;
                 }
             }
         }
@@ -839,10 +1769,103 @@ impl AnimationEngine {
         (cursor_line, buffer_line)
     }
 
+    /// Generate steps for typing an added line the way an editor with auto-indent
+    /// and auto-pairing would: the new line starts at the editor's indentation,
+    /// closing brackets are re-indented as they are typed, and auto-closed pairs
+    /// are skipped over instead of typed twice.
+    fn generate_smart_addition(
+        &mut self,
+        smart_edit: &SmartEdit,
+        line: usize,
+        new_line: usize,
+        content: &str,
+        prev_line: Option<&str>,
+    ) {
+        let indent_len = content
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .count();
+        let indent: String = content.chars().take(indent_len).collect();
+        let body: Vec<char> = content.chars().skip(indent_len).collect();
+
+        // Blank lines keep their exact indentation
+        let auto_indent = prev_line
+            .filter(|_| !content.trim().is_empty())
+            .map(|prev| smart_edit.auto_indent(prev));
+
+        let mut col = indent_len;
+        let mut typed = 0;
+        match auto_indent {
+            Some(auto_indent) if indent.starts_with(&auto_indent) => {
+                col = auto_indent.chars().count();
+                self.steps.push(AnimationStep::InsertLine {
+                    line,
+                    content: auto_indent,
+                    new_line,
+                });
+                // Type any indentation beyond what the editor inserted
+                for ch in indent.chars().skip(col) {
+                    self.steps.push(AnimationStep::InsertChar { line, col, ch });
+                    col += 1;
+                }
+            }
+            Some(auto_indent) => {
+                self.steps.push(AnimationStep::InsertLine {
+                    line,
+                    content: auto_indent,
+                    new_line,
+                });
+                let mut dedented = indent.clone();
+                if let Some(&first) = body.first().filter(|&&ch| smart_edit.is_closer(ch)) {
+                    // Typing a closing bracket dedents the line
+                    dedented.push(first);
+                    col += 1;
+                    typed = 1;
+                }
+                self.steps.push(AnimationStep::ReplaceLine {
+                    line,
+                    content: dedented,
+                });
+            }
+            None => {
+                self.steps.push(AnimationStep::InsertLine {
+                    line,
+                    content: indent,
+                    new_line,
+                });
+            }
+        }
+
+        let pairs = smart_edit.match_pairs(&body);
+        let mut auto_closed = vec![false; body.len()];
+        for (i, &ch) in body.iter().enumerate().skip(typed) {
+            if auto_closed[i] {
+                // The editor already inserted this closer; step over it
+                self.steps
+                    .push(AnimationStep::MoveCursor { line, col: col + 1 });
+            } else if let Some(close_idx) = pairs[i] {
+                auto_closed[close_idx] = true;
+                self.steps.push(AnimationStep::InsertPair {
+                    line,
+                    col,
+                    open: ch,
+                    close: body[close_idx],
+                });
+            } else {
+                self.steps.push(AnimationStep::InsertChar { line, col, ch });
+            }
+            col += 1;
+        }
+    }
+
     /// Updates animation state and returns true if display needs refresh.
     pub fn tick(&mut self) -> bool {
         self.update_cursor_blink();
 
+        if self.paused_at.is_some() {
+            return false;
+        }
+
         if self.is_paused() {
             return true;
         }
@@ -926,15 +1949,17 @@ impl AnimationEngine {
         // Calculate delay for next step with randomization for typing steps
         let mut rng = rand::rng();
         self.next_step_delay = match &step {
-            AnimationStep::InsertChar { .. } | AnimationStep::TerminalTypeChar { .. } => {
+            AnimationStep::InsertChar { .. }
+            | AnimationStep::InsertPair { .. }
+            | AnimationStep::TerminalTypeChar { .. } => {
                 // Add 70-130% variation to typing speed
                 let variation = rng.random_range(0.7..=1.3);
-                ((self.speed_ms as f64) * variation) as u64
+                (self.effective_speed_ms() * variation) as u64
             }
             AnimationStep::DialogTypeChar { .. } => {
                 // Dialog typing is slower (2x speed with variation)
                 let variation = rng.random_range(0.7..=1.3);
-                ((self.speed_ms as f64) * 2.0 * variation) as u64
+                (self.effective_speed_ms() * 2.0 * variation) as u64
             }
             AnimationStep::Pause { .. } => {
                 // Pause timing is driven by `pause_until`; don't add extra delay
@@ -942,7 +1967,7 @@ impl AnimationEngine {
             }
             _ => {
                 // Other steps use base speed
-                self.speed_ms
+                self.effective_speed_ms() as u64
             }
         };
 
@@ -953,15 +1978,47 @@ impl AnimationEngine {
                 self.buffer.cursor_line = line;
                 self.buffer.cursor_col = col + 1;
             }
-            AnimationStep::InsertLine { line, content } => {
+            AnimationStep::InsertPair {
+                line,
+                col,
+                open,
+                close,
+            } => {
+                self.active_pane = ActivePane::Editor;
+                self.buffer.insert_char(line, col, open);
+                self.buffer.insert_char(line, col + 1, close);
+                self.buffer.cursor_line = line;
+                self.buffer.cursor_col = col + 1;
+            }
+            AnimationStep::ReplaceLine { line, content } => {
+                self.active_pane = ActivePane::Editor;
+                self.buffer.cursor_line = line;
+                self.buffer.cursor_col = content.chars().count();
+                if let Some(existing) = self.buffer.lines.get_mut(line) {
+                    *existing = content;
+                }
+            }
+            AnimationStep::PasteLines {
+                line,
+                remove,
+                lines,
+                new_start,
+            } => {
+                self.active_pane = ActivePane::Editor;
+                self.buffer.cursor_line = self.buffer.splice_lines(line, remove, lines, new_start);
+                self.buffer.cursor_col = 0;
+            }
+            AnimationStep::InsertLine {
+                line,
+                content,
+                new_line,
+            } => {
                 self.active_pane = ActivePane::Editor;
                 let content_len = content.chars().count();
-                self.buffer.insert_line(line, content);
+                self.buffer
+                    .insert_line(line, content, LineOrigin::New(new_line));
                 self.buffer.cursor_line = line;
                 self.buffer.cursor_col = content_len;
-
-                // Track line offset for old_highlights mapping
-                self.line_offset += 1;
             }
             AnimationStep::DeleteLine { line } => {
                 self.active_pane = ActivePane::Editor;
@@ -974,9 +2031,6 @@ impl AnimationEngine {
                     .get(line)
                     .map(|l| l.chars().take_while(|c| c.is_whitespace()).count())
                     .unwrap_or(0);
-
-                // Track line offset for old_highlights mapping
-                self.line_offset -= 1;
             }
             AnimationStep::MoveCursor { line, col } => {
                 self.active_pane = ActivePane::Editor;
@@ -984,7 +2038,7 @@ impl AnimationEngine {
                 self.buffer.cursor_col = col;
             }
             AnimationStep::Pause { multiplier } => {
-                let duration_ms = (self.speed_ms as f64 * multiplier) as u64;
+                let duration_ms = (self.effective_speed_ms() * multiplier) as u64;
                 self.pause_until = Some(Instant::now() + Duration::from_millis(duration_ms));
             }
             AnimationStep::OpenFileDialogStart => {
@@ -1004,10 +2058,15 @@ impl AnimationEngine {
                 // Clear dialog when file is actually switched
                 self.dialog_title = None;
                 self.dialog_typing_text = String::new();
+                // Keep the file being left so its edits survive switching back to it
+                if self.current_file_path.is_some() {
+                    self.saved_buffers
+                        .insert(self.current_file_index, self.buffer.clone());
+                }
+
                 // Switch to new file
                 self.current_file_index = file_index;
                 self.current_file_path = Some(path.clone());
-                self.buffer = EditorBuffer::from_content(&old_content);
 
                 // Update typing speed based on file-specific rules
                 self.speed_ms = self.get_speed_for_file(&path);
@@ -1016,31 +2075,43 @@ impl AnimationEngine {
                 // This will clear language settings if not supported
                 self.highlighter.borrow_mut().set_language_from_path(&path);
 
-                // Pre-calculate highlights for both old and new content
-                self.buffer.old_highlights = self.highlighter.borrow_mut().highlight(&old_content);
-                self.buffer.new_highlights = self.highlighter.borrow_mut().highlight(&new_content);
-
-                // Store content lines for byte offset calculation
-                self.buffer.old_content_lines = if old_content.is_empty() {
-                    vec![String::new()]
-                } else {
-                    old_content.lines().map(|s| s.to_string()).collect()
-                };
-                self.buffer.new_content_lines = if new_content.is_empty() {
-                    vec![String::new()]
+                // Resume a file left mid-edit with its highlights already calculated
+                if let Some(buffer) = self.saved_buffers.remove(&file_index) {
+                    self.buffer = buffer;
                 } else {
-                    new_content.lines().map(|s| s.to_string()).collect()
-                };
+                    self.buffer = EditorBuffer::from_content(&old_content);
+
+                    // Pre-calculate highlights for both old and new content, unless parsing
+                    // the file would stall playback
+                    let size = old_content.len().max(new_content.len());
+                    if size <= crate::git::large_file_limits().max_blob_size {
+                        self.buffer.old_highlights =
+                            self.highlighter.borrow_mut().highlight(&old_content);
+                        self.buffer.new_highlights =
+                            self.highlighter.borrow_mut().highlight(&new_content);
+                    }
 
-                // Pre-calculate line byte offsets (handles CRLF correctly)
-                self.buffer.old_content_line_offsets = Self::calculate_line_offsets(&old_content);
-                self.buffer.new_content_line_offsets = Self::calculate_line_offsets(&new_content);
+                    // Store content lines for byte offset calculation
+                    self.buffer.old_content_lines = if old_content.is_empty() {
+                        vec![String::new()]
+                    } else {
+                        old_content.lines().map(|s| s.to_string()).collect()
+                    };
+                    self.buffer.new_content_lines = if new_content.is_empty() {
+                        vec![String::new()]
+                    } else {
+                        new_content.lines().map(|s| s.to_string()).collect()
+                    };
 
-                // Initialize cached_highlights with old_highlights
-                self.buffer.cached_highlights = self.buffer.old_highlights.clone();
+                    // Pre-calculate line byte offsets (handles CRLF correctly)
+                    self.buffer.old_content_line_offsets =
+                        Self::calculate_line_offsets(&old_content);
+                    self.buffer.new_content_line_offsets =
+                        Self::calculate_line_offsets(&new_content);
 
-                // Reset line offset
-                self.line_offset = 0;
+                    // Initialize cached_highlights with old_highlights
+                    self.buffer.cached_highlights = self.buffer.old_highlights.clone();
+                }
             }
             AnimationStep::TerminalPrompt => {
                 self.active_pane = ActivePane::Terminal;
@@ -1076,9 +2147,10 @@ impl AnimationEngine {
         self.update_scroll();
     }
 
-    fn calculate_line_display_height(&self, line: &str) -> usize {
+    /// Width of the editor's text column, or 0 when lines don't wrap
+    fn wrap_width(&self) -> usize {
         if self.content_width == 0 {
-            return 1;
+            return 0;
         }
 
         // Calculate text area width (excluding line numbers, padding, etc.)
@@ -1089,14 +2161,17 @@ impl AnimationEngine {
         let right_padding = 2;
         let fixed_width = left_padding + line_num_and_space + separator + right_padding;
 
-        let text_width = self.content_width.saturating_sub(fixed_width);
-        if text_width == 0 {
+        self.content_width.saturating_sub(fixed_width)
+    }
+
+    fn calculate_line_display_height(line: &str, wrap_width: usize) -> usize {
+        if wrap_width == 0 {
             return 1;
         }
 
         // Calculate how many lines this text will take when wrapped (using display width)
         let display_width = line.width();
-        display_width.div_ceil(text_width).max(1)
+        display_width.div_ceil(wrap_width).max(1)
     }
 
     fn update_scroll(&mut self) {
@@ -1109,10 +2184,11 @@ impl AnimationEngine {
         // Calculate display line positions for each logical line
         let mut display_line_positions = Vec::with_capacity(self.buffer.lines.len());
         let mut current_display_line = 0;
+        let wrap_width = self.wrap_width();
 
         for line in &self.buffer.lines {
             display_line_positions.push(current_display_line);
-            current_display_line += self.calculate_line_display_height(line);
+            current_display_line += Self::calculate_line_display_height(line, wrap_width);
         }
 
         let total_display_lines = current_display_line;
@@ -1143,8 +2219,596 @@ impl AnimationEngine {
         self.buffer.scroll_offset = logical_offset;
     }
 
+    /// Pauses or resumes playback. Time spent paused does not count towards step delays.
+    pub fn toggle_pause(&mut self) {
+        match self.paused_at.take() {
+            Some(paused_at) => {
+                let paused_for = paused_at.elapsed();
+                self.pause_until = self.pause_until.map(|until| until + paused_for);
+                self.last_update += paused_for;
+            }
+            None => self.paused_at = Some(Instant::now()),
+        }
+    }
+
+    /// Returns true if the viewer paused playback.
+    pub fn is_paused_by_viewer(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Scrolls the editor by `lines`, up when negative.
+    pub fn scroll_editor(&mut self, lines: isize) {
+        let max_offset = self.buffer.lines.len().saturating_sub(1);
+        self.buffer.scroll_offset = self
+            .buffer
+            .scroll_offset
+            .saturating_add_signed(lines)
+            .min(max_offset);
+    }
+
+    /// Fast-forwards to the next `SwitchFile` step of a file, restarting the commit if
+    /// the file is not opened again, and runs it. Returns `false` if the file is never
+    /// opened in the editor.
+    pub fn jump_to_file(&mut self, file_index: usize) -> bool {
+        // File indices refer to the current commit, not one still waiting to start
+        if self.pending_metadata.is_some() {
+            return false;
+        }
+        let find = |steps: &[AnimationStep], from: usize| {
+            steps[from.min(steps.len())..]
+                .iter()
+                .position(|step| {
+                    matches!(step, AnimationStep::SwitchFile { file_index: i, .. } if *i == file_index)
+                })
+                .map(|position| from + position)
+        };
+        if find(&self.steps, 0).is_none() {
+            return false;
+        }
+
+        let target = match find(&self.steps, self.current_step) {
+            Some(target) => target,
+            None => {
+                let Some(metadata) = self.current_metadata.clone() else {
+                    return false;
+                };
+                // The replayed intro prints its terminal lines again
+                self.terminal_lines = std::mem::take(&mut self.commit_start_terminal_lines);
+                self.load_commit(&metadata);
+                match find(&self.steps, 0) {
+                    Some(target) => target,
+                    None => return false,
+                }
+            }
+        };
+
+        while self.current_step <= target {
+            self.run_next_step();
+        }
+        // Resume typing after the usual delay, counted from the pause if paused
+        self.last_update = self.paused_at.unwrap_or_else(Instant::now);
+        true
+    }
+
     /// Returns true if the animation has completed.
     pub fn is_finished(&self) -> bool {
         self.state == AnimationState::Finished
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{LineChange, SignatureStatus};
+    use chrono::Utc;
+
+    fn line(change_type: LineChangeType, content: &str) -> LineChange {
+        LineChange {
+            change_type,
+            content: content.to_string(),
+            old_line_no: None,
+            new_line_no: None,
+        }
+    }
+
+    fn commit_with(change: FileChange) -> CommitMetadata {
+        CommitMetadata {
+            hash: "0123456789abcdef".to_string(),
+            author: "Test User".to_string(),
+            committer: None,
+            co_authors: Vec::new(),
+            reviewers: Vec::new(),
+            refs: Vec::new(),
+            signature: SignatureStatus::Unsigned,
+            date: Utc::now(),
+            message: "Test commit".to_string(),
+            changes: vec![change],
+        }
+    }
+
+    fn replay(engine: &mut AnimationEngine) -> Vec<String> {
+        let mut file_lines = Vec::new();
+        while engine.current_step < engine.steps.len() {
+            let step = engine.steps[engine.current_step].clone();
+            engine.execute_step(step);
+            engine.current_step += 1;
+            if engine.current_file_path.is_some() {
+                file_lines = engine.buffer.lines.clone();
+            }
+        }
+        file_lines
+    }
+
+    #[test]
+    fn test_smart_edit_produces_final_content() {
+        let old_content = "fn main() {\n}\n";
+        let new_content =
+            "fn main() {\n    let v = vec![1, (2)];\n    if x { y(\"a)\") }\n    z('c');\n}\n";
+        let change =
+            FileChange::from_contents("src/main.rs", Some(old_content), Some(new_content)).unwrap();
+
+        let mut engine = AnimationEngine::new(1);
+        engine.set_smart_edit(true);
+        engine.load_commit(&commit_with(change));
+
+        assert!(engine
+            .steps
+            .iter()
+            .any(|s| matches!(s, AnimationStep::InsertPair { open: '(', .. })));
+        assert_eq!(replay(&mut engine), new_content.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_smart_edit_dedents_closing_bracket() {
+        let old_content = "fn f() {\n}\n";
+        let new_content = "fn f() {\n  if a {\n    b();\n  }\n}\n";
+        let change =
+            FileChange::from_contents("f.rs", Some(old_content), Some(new_content)).unwrap();
+
+        let mut engine = AnimationEngine::new(1);
+        engine.set_smart_edit(true);
+        engine.load_commit(&commit_with(change));
+
+        assert!(engine.steps.iter().any(|s| matches!(
+            s,
+            AnimationStep::ReplaceLine { content, .. } if content == "  }"
+        )));
+        assert_eq!(replay(&mut engine), new_content.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_duration_target_scales_estimate() {
+        let change = FileChange::from_contents("a.txt", None, Some("hello world\n")).unwrap();
+
+        let mut engine = AnimationEngine::new(30);
+        engine.set_duration_target(DurationTarget {
+            target: Some(Duration::from_secs(30)),
+            ..Default::default()
+        });
+        engine.load_commit(&commit_with(change));
+
+        let estimate = engine.estimate_duration_ms();
+        assert!(estimate.abs_diff(30_000) <= 1, "estimate was {}", estimate);
+    }
+
+    #[test]
+    fn test_estimate_starts_from_base_speed_after_speed_rule_file() {
+        let change = FileChange::from_contents("slow.txt", None, Some("hello\n")).unwrap();
+        let metadata = commit_with(change);
+
+        let mut engine = AnimationEngine::new(10);
+        engine.set_speed_rules(vec![SpeedRule::parse("*.txt:50").unwrap()]);
+        engine.load_commit(&metadata);
+        let first = engine.timeline();
+        assert_eq!(first[0].duration_ms, 10);
+
+        // Playing the commit leaves the engine at the speed rule's speed
+        replay(&mut engine);
+        engine.load_commit(&metadata);
+        let second = engine.timeline();
+        assert_eq!(second[0].duration_ms, 10);
+        assert_eq!(
+            first.last().map(|e| e.at_ms),
+            second.last().map(|e| e.at_ms)
+        );
+    }
+
+    #[test]
+    fn test_timeline_timestamps_are_cumulative() {
+        let change = modified(
+            "a.txt",
+            &["a"],
+            &["a", "b"],
+            vec![DiffHunk {
+                old_start: 1,
+                old_lines: 1,
+                new_start: 1,
+                new_lines: 2,
+                lines: vec![
+                    line(LineChangeType::Context, "a"),
+                    line(LineChangeType::Addition, "b"),
+                ],
+            }],
+        );
+
+        let mut engine = AnimationEngine::new(10);
+        engine.load_commit(&commit_with(change));
+        let timeline = engine.timeline();
+
+        assert_eq!(timeline.len(), engine.steps.len());
+        assert_eq!(timeline[0].at_ms, 0);
+        for pair in timeline.windows(2) {
+            let drift = (pair[0].at_ms + pair[0].duration_ms).abs_diff(pair[1].at_ms);
+            assert!(drift <= 1, "{:?} then {:?}", pair[0], pair[1]);
+        }
+        let pause = timeline
+            .iter()
+            .find(|e| matches!(e.step, AnimationStep::Pause { multiplier } if multiplier == 50.0))
+            .unwrap();
+        assert_eq!(pause.duration_ms, 500);
+
+        let json = serde_json::to_value(&timeline[0]).unwrap();
+        assert_eq!(json["type"], "terminal_prompt");
+        assert_eq!(json["at_ms"], 0);
+    }
+
+    #[test]
+    fn test_huge_hunk_is_pasted_in_one_step() {
+        let added: Vec<String> = (0..1842).map(|i| format!("line {}", i)).collect();
+        let old_content = "start\nend\n";
+        let new_content = format!("start\n{}\nend\n", added.join("\n"));
+
+        let change =
+            FileChange::from_contents("big.txt", Some(old_content), Some(&new_content)).unwrap();
+
+        let mut engine = AnimationEngine::new(1);
+        engine.load_commit(&commit_with(change));
+
+        assert!(engine.steps.iter().any(|s| matches!(
+            s,
+            AnimationStep::TerminalOutput { text } if text == "⏩ fast-forwarding 1,842 lines"
+        )));
+        assert!(!engine
+            .steps
+            .iter()
+            .any(|s| matches!(s, AnimationStep::InsertChar { .. })));
+        assert_eq!(replay(&mut engine), new_content.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_oversized_file_keeps_content_and_fast_forwards_later_hunks() {
+        // Few changed lines, but more bytes than `max_blob_size`
+        let limits = crate::git::large_file_limits();
+        let filler = "x".repeat(99);
+        let count = limits.max_blob_size / 100 + 1;
+        let old: Vec<String> = (0..count).map(|i| format!("{:05}{}", i, filler)).collect();
+        let mut new = old.clone();
+        let mut hunks = Vec::new();
+        for i in 0..limits.full_hunks + 2 {
+            let at = i * 1000;
+            new[at] = format!("changed {}", i);
+            hunks.push(DiffHunk {
+                old_start: at + 1,
+                old_lines: 1,
+                new_start: at + 1,
+                new_lines: 1,
+                lines: vec![
+                    line(LineChangeType::Deletion, &old[at]),
+                    line(LineChangeType::Addition, &new[at]),
+                ],
+            });
+        }
+        let old_lines: Vec<&str> = old.iter().map(String::as_str).collect();
+        let new_lines: Vec<&str> = new.iter().map(String::as_str).collect();
+        let change = modified("big.txt", &old_lines, &new_lines, hunks);
+
+        let mut engine = AnimationEngine::new(1);
+        engine.load_commit(&commit_with(change));
+
+        assert!(engine.steps.iter().any(|s| matches!(
+            s,
+            AnimationStep::TerminalOutput { text } if text == "⏩ fast-forwarding 4 lines"
+        )));
+        let pasted = engine
+            .steps
+            .iter()
+            .filter(|s| matches!(s, AnimationStep::PasteLines { .. }))
+            .count();
+        assert_eq!(pasted, 2);
+    }
+
+    #[test]
+    fn test_deleted_and_renamed_files_are_animated() {
+        let deleted = FileChange::from_contents("gone.txt", Some("one\ntwo\n"), None).unwrap();
+        let mut engine = AnimationEngine::new(1);
+        engine.set_deleted_file_mode(DeletedFileMode::LineByLine);
+        engine.load_commit(&commit_with(deleted));
+        assert_eq!(
+            engine
+                .steps
+                .iter()
+                .filter(|s| matches!(s, AnimationStep::DeleteLine { .. }))
+                .count(),
+            2
+        );
+        assert_eq!(replay(&mut engine), vec![""]);
+
+        let renamed = FileChange {
+            old_path: Some("old.txt".to_string()),
+            status: FileStatus::Renamed,
+            ..FileChange::from_contents("new.txt", Some("a\nb\n"), Some("a\nc\n")).unwrap()
+        };
+        let mut engine = AnimationEngine::new(1);
+        engine.set_animate_renames(true);
+        engine.load_commit(&commit_with(renamed));
+        assert_eq!(replay(&mut engine), vec!["a", "c"]);
+    }
+
+    /// Build a modified file change from its old and new lines, with the given hunks
+    fn modified(path: &str, old: &[&str], new: &[&str], hunks: Vec<DiffHunk>) -> FileChange {
+        let old = format!("{}\n", old.join("\n"));
+        let new = format!("{}\n", new.join("\n"));
+        FileChange {
+            hunks,
+            ..FileChange::from_contents(path, Some(&old), Some(&new)).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_organic_order_bounces_to_call_site_and_ends_with_new_content() {
+        let lib_old = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        let lib_new = [
+            "a",
+            "b",
+            "c",
+            "fn parse_args() {}",
+            "d",
+            "e",
+            "f",
+            "g",
+            "h",
+            "i",
+            "x",
+            "j",
+        ];
+        let lib = modified(
+            "src/lib.rs",
+            &lib_old,
+            &lib_new,
+            vec![
+                DiffHunk {
+                    old_start: 1,
+                    old_lines: 6,
+                    new_start: 1,
+                    new_lines: 7,
+                    lines: vec![
+                        line(LineChangeType::Context, "a"),
+                        line(LineChangeType::Context, "b"),
+                        line(LineChangeType::Context, "c"),
+                        line(LineChangeType::Addition, "fn parse_args() {}"),
+                        line(LineChangeType::Context, "d"),
+                        line(LineChangeType::Context, "e"),
+                        line(LineChangeType::Context, "f"),
+                    ],
+                },
+                DiffHunk {
+                    old_start: 7,
+                    old_lines: 4,
+                    new_start: 8,
+                    new_lines: 5,
+                    lines: vec![
+                        line(LineChangeType::Context, "g"),
+                        line(LineChangeType::Context, "h"),
+                        line(LineChangeType::Context, "i"),
+                        line(LineChangeType::Addition, "x"),
+                        line(LineChangeType::Context, "j"),
+                    ],
+                },
+            ],
+        );
+        let main = modified(
+            "src/main.rs",
+            &["fn main() {", "}"],
+            &["fn main() {", "    parse_args();", "}"],
+            vec![DiffHunk {
+                old_start: 1,
+                old_lines: 2,
+                new_start: 1,
+                new_lines: 3,
+                lines: vec![
+                    line(LineChangeType::Context, "fn main() {"),
+                    line(LineChangeType::Addition, "    parse_args();"),
+                    line(LineChangeType::Context, "}"),
+                ],
+            }],
+        );
+        let metadata = CommitMetadata {
+            hash: "0123456789abcdef".to_string(),
+            author: "Test User".to_string(),
+            committer: None,
+            co_authors: Vec::new(),
+            reviewers: Vec::new(),
+            refs: Vec::new(),
+            signature: SignatureStatus::Unsigned,
+            date: Utc::now(),
+            message: "Test commit".to_string(),
+            changes: vec![lib, main],
+        };
+
+        let plan = plan_organic_edits(&metadata.changes, &[0, 1]);
+        assert_eq!(plan, vec![(0, vec![0]), (1, vec![0]), (0, vec![1])]);
+
+        let mut engine = AnimationEngine::new(1);
+        engine.set_edit_order(EditOrder::Organic);
+        assert_eq!(engine.verify_commit(&metadata), Vec::new());
+    }
+
+    #[test]
+    fn test_verify_added_and_emptied_files() {
+        let added = FileChange {
+            status: FileStatus::Added,
+            old_content: None,
+            hunks: vec![DiffHunk {
+                old_start: 0,
+                old_lines: 0,
+                new_start: 1,
+                new_lines: 2,
+                lines: vec![
+                    line(LineChangeType::Addition, "one"),
+                    line(LineChangeType::Addition, "two"),
+                ],
+            }],
+            ..modified("added.txt", &[], &["one", "two"], Vec::new())
+        };
+        let emptied = FileChange {
+            new_content: Some(String::new()),
+            ..modified(
+                "emptied.txt",
+                &["gone"],
+                &[],
+                vec![DiffHunk {
+                    old_start: 1,
+                    old_lines: 1,
+                    new_start: 0,
+                    new_lines: 0,
+                    lines: vec![line(LineChangeType::Deletion, "gone")],
+                }],
+            )
+        };
+        let mut metadata = commit_with(added);
+        metadata.changes.push(emptied);
+
+        let mut engine = AnimationEngine::new(1);
+        assert_eq!(engine.verify_commit(&metadata), Vec::new());
+    }
+
+    #[test]
+    fn test_verify_reports_first_divergent_line() {
+        // The hunk forgets to delete "b", so the buffer keeps a stale line
+        let change = modified(
+            "drift.txt",
+            &["a", "b"],
+            &["a", "c"],
+            vec![DiffHunk {
+                old_start: 1,
+                old_lines: 2,
+                new_start: 1,
+                new_lines: 2,
+                lines: vec![
+                    line(LineChangeType::Context, "a"),
+                    line(LineChangeType::Addition, "c"),
+                ],
+            }],
+        );
+
+        let mut engine = AnimationEngine::new(1);
+        assert_eq!(
+            engine.verify_commit(&commit_with(change)),
+            vec![BufferMismatch {
+                path: "drift.txt".to_string(),
+                line: 3,
+                expected: None,
+                actual: Some("b".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_old_scroll_offset_follows_buffer() {
+        let mut buffer = EditorBuffer::from_content("a\nb\nc\nd\ne\n");
+        // Replace "b" and "c" with three new lines
+        buffer.splice_lines(1, 2, vec!["x".into(), "y".into(), "z".into()], 1);
+
+        buffer.scroll_offset = 0;
+        assert_eq!(buffer.old_scroll_offset(), 0);
+        // Tops on a new line align with the next old line below them
+        buffer.scroll_offset = 2;
+        assert_eq!(buffer.old_scroll_offset(), 1);
+        buffer.scroll_offset = 4;
+        assert_eq!(buffer.old_scroll_offset(), 3);
+
+        // Only new lines from the top: continue after the last old line above
+        buffer.splice_lines(4, 2, vec!["w".into()], 4);
+        assert_eq!(buffer.old_scroll_offset(), 1);
+    }
+
+    #[test]
+    fn test_inserted_lines_replacing_deletions_are_marked_modified() {
+        use LineMarker::{Added, Modified};
+
+        let mut buffer = EditorBuffer::from_content("a\nb\nc\nd\n");
+        // Replace "b" and "c" with three lines, as a hunk plays them
+        buffer.delete_line(1);
+        buffer.delete_line(1);
+        for (i, content) in ["x", "y", "z"].into_iter().enumerate() {
+            buffer.insert_line(1 + i, content.to_string(), LineOrigin::New(1 + i));
+        }
+        // A pure deletion followed by an addition further down
+        buffer.delete_line(0);
+        buffer.insert_line(4, "e".to_string(), LineOrigin::New(4));
+
+        assert_eq!(buffer.lines, ["x", "y", "z", "d", "e"]);
+        assert_eq!(
+            buffer.line_markers,
+            [
+                Some(Modified),
+                Some(Modified),
+                Some(Added),
+                None,
+                Some(Added)
+            ]
+        );
+
+        buffer.splice_lines(3, 1, vec!["p".into(), "q".into()], 3);
+        assert_eq!(
+            buffer.line_markers[3..],
+            [Some(Modified), Some(Added), Some(Added)]
+        );
+    }
+
+    #[test]
+    fn test_jump_to_file_opens_it_and_restarts_for_earlier_files() {
+        let file = |path: &str, old: &str, new: &str| {
+            modified(
+                path,
+                &[old],
+                &[new],
+                vec![DiffHunk {
+                    old_start: 1,
+                    old_lines: 1,
+                    new_start: 1,
+                    new_lines: 1,
+                    lines: vec![
+                        line(LineChangeType::Deletion, old),
+                        line(LineChangeType::Addition, new),
+                    ],
+                }],
+            )
+        };
+        let mut metadata = commit_with(file("a.txt", "a1", "a2"));
+        metadata.changes.push(file("b.txt", "b1", "b2"));
+
+        let mut engine = AnimationEngine::new(1);
+        engine.load_commit(&metadata);
+        // Start the commit so its metadata is current
+        while engine.current_metadata().is_none() {
+            engine.run_next_step();
+        }
+
+        assert!(engine.jump_to_file(0));
+        let terminal_lines = engine.terminal_lines.clone();
+
+        assert!(engine.jump_to_file(1));
+        assert_eq!(engine.current_file_path.as_deref(), Some("b.txt"));
+        assert_eq!(engine.buffer.lines, vec!["b1"]);
+
+        // a.txt is not opened again, so the commit starts over
+        assert!(engine.jump_to_file(0));
+        assert_eq!(engine.current_file_path.as_deref(), Some("a.txt"));
+        assert_eq!(engine.buffer.lines, vec!["a1"]);
+        // without printing the intro to the terminal twice
+        assert_eq!(engine.terminal_lines, terminal_lines);
+
+        assert!(!engine.jump_to_file(5));
+    }
+}
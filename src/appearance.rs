@@ -0,0 +1,330 @@
+use std::env;
+use std::io::IsTerminal;
+
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveTime};
+
+use crate::config::{AutoTheme, ThemeConfig};
+use crate::theme::{ColorMode, Theme};
+
+/// Whether the terminal has a light or a dark background
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    /// Ask the terminal for its background color, then fall back to `COLORFGBG`,
+    /// then assume a dark background
+    pub fn detect() -> Self {
+        Self::resolve(Self::query())
+    }
+
+    /// The terminal's answer to a background color query, if it gives one
+    fn query() -> Option<Self> {
+        if std::io::stdout().is_terminal() {
+            query_background().map(Self::from_rgb)
+        } else {
+            None
+        }
+    }
+
+    /// The queried appearance, or else the one from `COLORFGBG`, or else dark
+    fn resolve(queried: Option<Self>) -> Self {
+        queried
+            .or_else(|| {
+                env::var("COLORFGBG")
+                    .ok()
+                    .and_then(|v| Self::from_colorfgbg(&v))
+            })
+            .unwrap_or(Self::Dark)
+    }
+
+    fn from_rgb([r, g, b]: [u8; 3]) -> Self {
+        let luminance = 0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b);
+        if luminance > 127.5 {
+            Self::Light
+        } else {
+            Self::Dark
+        }
+    }
+
+    /// `COLORFGBG` is `fg;bg` (sometimes `fg;default;bg`) with ANSI color numbers
+    fn from_colorfgbg(value: &str) -> Option<Self> {
+        match value.rsplit(';').next()?.parse::<u8>().ok()? {
+            7 | 9..=15 => Some(Self::Light),
+            0..=6 | 8 => Some(Self::Dark),
+            _ => None,
+        }
+    }
+}
+
+/// Local time range in which the light theme is used, e.g. `07:00-19:00`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightHours {
+    from: NaiveTime,
+    until: NaiveTime,
+}
+
+impl LightHours {
+    pub fn parse(value: &str) -> Result<Self> {
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .with_context(|| format!("Invalid time '{}' (expected HH:MM)", time.trim()))
+        };
+        let (from, until) = value
+            .split_once('-')
+            .with_context(|| format!("Invalid light_hours '{}' (expected HH:MM-HH:MM)", value))?;
+        Ok(Self {
+            from: parse_time(from)?,
+            until: parse_time(until)?,
+        })
+    }
+
+    /// Ranges may wrap around midnight, e.g. `22:00-06:00`
+    fn appearance_at(&self, time: NaiveTime) -> Appearance {
+        let light = if self.from <= self.until {
+            self.from <= time && time < self.until
+        } else {
+            time >= self.from || time < self.until
+        };
+        if light {
+            Appearance::Light
+        } else {
+            Appearance::Dark
+        }
+    }
+}
+
+impl AutoTheme {
+    /// Name of the theme to use now, from the light hours if set, or else the terminal
+    pub fn current(&self) -> Result<&str> {
+        self.pick(Appearance::detect)
+    }
+
+    /// Name of the theme to use now, calling `detect` only when there are no light hours
+    fn pick(&self, detect: impl FnOnce() -> Appearance) -> Result<&str> {
+        let appearance = match &self.light_hours {
+            Some(hours) => LightHours::parse(hours)?.appearance_at(Local::now().time()),
+            None => detect(),
+        };
+        Ok(match appearance {
+            Appearance::Light => &self.light,
+            Appearance::Dark => &self.dark,
+        })
+    }
+}
+
+impl ThemeConfig {
+    /// Name of the theme to use now
+    pub fn current(&self) -> Result<&str> {
+        match self {
+            Self::Name(name) => Ok(name),
+            Self::Auto(auto) => auto.current(),
+        }
+    }
+}
+
+/// Load the theme named on the command line, or else the configured one, with a switcher
+/// when the config has a light and a dark theme
+pub fn load_theme(
+    name: Option<&str>,
+    config: &ThemeConfig,
+    color: ColorMode,
+    background: bool,
+) -> Result<(Theme, Option<ThemeSwitcher>)> {
+    if let (None, ThemeConfig::Auto(auto)) = (name, config) {
+        let (switcher, theme) = ThemeSwitcher::new(auto.clone(), color, background)?;
+        return Ok((theme, Some(switcher)));
+    }
+
+    let name = match name {
+        Some(name) => name,
+        None => config.current()?,
+    };
+    let theme = Theme::load(name)?.with_color_mode(color);
+    let theme = if background {
+        theme
+    } else {
+        theme.with_transparent_background()
+    };
+    Ok((theme, None))
+}
+
+/// Switches between the light and dark theme as the appearance changes
+pub struct ThemeSwitcher {
+    auto: AutoTheme,
+    current: String,
+    /// The terminal's background, asked once at startup: a query during playback would
+    /// race with key presses and turn late replies into key events
+    terminal: Option<Appearance>,
+    color: ColorMode,
+    background: bool,
+}
+
+impl ThemeSwitcher {
+    /// Loads the theme for the current appearance
+    pub fn new(auto: AutoTheme, color: ColorMode, background: bool) -> Result<(Self, Theme)> {
+        // Fail early on a typo in the theme that isn't shown yet
+        for name in [&auto.light, &auto.dark] {
+            Theme::load(name)?;
+        }
+        let terminal = match auto.light_hours {
+            Some(_) => None,
+            None => Appearance::query(),
+        };
+        let switcher = Self {
+            current: auto.pick(|| Appearance::resolve(terminal))?.to_string(),
+            terminal,
+            auto,
+            color,
+            background,
+        };
+        let theme = switcher.load()?;
+        Ok((switcher, theme))
+    }
+
+    /// The theme to switch to, if the appearance changed since the last call. Only the
+    /// light hours and `COLORFGBG` are checked again, never the terminal.
+    pub fn update(&mut self) -> Option<Theme> {
+        let terminal = self.terminal;
+        let name = self.auto.pick(|| Appearance::resolve(terminal)).ok()?;
+        if name == self.current {
+            return None;
+        }
+        self.current = name.to_string();
+        self.load().ok()
+    }
+
+    fn load(&self) -> Result<Theme> {
+        let theme = Theme::load(&self.current)?.with_color_mode(self.color);
+        Ok(if self.background {
+            theme
+        } else {
+            theme.with_transparent_background()
+        })
+    }
+}
+
+/// Query the background color with OSC 11. A device attributes query follows it, which
+/// every terminal answers, so terminals that ignore OSC 11 don't wait for the timeout.
+#[cfg(unix)]
+fn query_background() -> Option<[u8; 3]> {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::fd::AsRawFd;
+    use std::time::{Duration, Instant};
+
+    use crossterm::terminal;
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    // Raw mode keeps the reply from being echoed or line buffered
+    let was_raw = terminal::is_raw_mode_enabled().ok()?;
+    if !was_raw {
+        terminal::enable_raw_mode().ok()?;
+    }
+
+    let mut response = Vec::new();
+    if tty.write_all(b"\x1b]11;?\x1b\\\x1b[c").is_ok() && tty.flush().is_ok() {
+        let deadline = Instant::now() + Duration::from_millis(200);
+        let mut buf = [0u8; 64];
+        while !has_device_attributes(&response) {
+            let Some(remaining) = deadline.checked_duration_since(Instant::now()) else {
+                break;
+            };
+            let mut fds = libc::pollfd {
+                fd: tty.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: `fds` is a valid pollfd for the open tty, and the count is 1
+            let ready = unsafe { libc::poll(&mut fds, 1, remaining.as_millis() as i32) };
+            if ready <= 0 {
+                break;
+            }
+            match tty.read(&mut buf) {
+                Ok(n) if n > 0 => response.extend_from_slice(&buf[..n]),
+                _ => break,
+            }
+        }
+    }
+
+    if !was_raw {
+        terminal::disable_raw_mode().ok();
+    }
+    parse_osc11(&String::from_utf8_lossy(&response))
+}
+
+#[cfg(not(unix))]
+fn query_background() -> Option<[u8; 3]> {
+    None
+}
+
+/// Whether a device attributes reply (`ESC [ ? ... c`) has arrived
+#[cfg(unix)]
+fn has_device_attributes(response: &[u8]) -> bool {
+    response
+        .windows(3)
+        .position(|w| w == b"\x1b[?")
+        .is_some_and(|start| response[start..].contains(&b'c'))
+}
+
+/// Parse `ESC ] 11 ; rgb:RRRR/GGGG/BBBB` with 1 to 4 hex digits per channel
+fn parse_osc11(response: &str) -> Option<[u8; 3]> {
+    let start = response.find("]11;rgb:")? + "]11;rgb:".len();
+    let mut channels = response[start..].split('/').map(|channel| {
+        let digits: String = channel
+            .chars()
+            .take_while(|c| c.is_ascii_hexdigit())
+            .collect();
+        let value = u32::from_str_radix(&digits, 16).ok()?;
+        let max = 16u32.checked_pow(digits.len() as u32)? - 1;
+        (!digits.is_empty() && digits.len() <= 4).then(|| (value * 255 / max) as u8)
+    });
+    Some([channels.next()??, channels.next()??, channels.next()??])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_terminal_background() {
+        assert_eq!(
+            parse_osc11("\x1b]11;rgb:ffff/ffff/ffff\x1b\\\x1b[?62;c"),
+            Some([255, 255, 255])
+        );
+        assert_eq!(parse_osc11("\x1b]11;rgb:1a/1b/26\x07"), Some([26, 27, 38]));
+        assert_eq!(parse_osc11("\x1b[?62;c"), None);
+
+        assert_eq!(Appearance::from_rgb([253, 246, 227]), Appearance::Light);
+        assert_eq!(Appearance::from_rgb([26, 27, 38]), Appearance::Dark);
+        assert_eq!(Appearance::from_colorfgbg("0;15"), Some(Appearance::Light));
+        assert_eq!(
+            Appearance::from_colorfgbg("15;default;0"),
+            Some(Appearance::Dark)
+        );
+        assert_eq!(Appearance::from_colorfgbg("default"), None);
+    }
+
+    #[test]
+    fn test_light_hours_may_wrap_around_midnight() {
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+
+        let day = LightHours::parse("07:00-19:30").unwrap();
+        assert_eq!(day.appearance_at(at(7, 0)), Appearance::Light);
+        assert_eq!(day.appearance_at(at(19, 30)), Appearance::Dark);
+        assert_eq!(day.appearance_at(at(3, 0)), Appearance::Dark);
+
+        let night_shift = LightHours::parse("22:00 - 06:00").unwrap();
+        assert_eq!(night_shift.appearance_at(at(23, 0)), Appearance::Light);
+        assert_eq!(night_shift.appearance_at(at(12, 0)), Appearance::Dark);
+
+        assert!(LightHours::parse("7am-7pm").is_err());
+    }
+}
@@ -1,12 +1,20 @@
 use anyhow::{Context, Result};
+use clap::ValueEnum;
+use globset::GlobBuilder;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::git::LargeFileLimits;
+use crate::theme::ColorMode;
+use crate::timing::{parse_duration, DurationTarget, TimingConfig};
+use crate::ui::PaneLayout;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default = "default_theme")]
-    pub theme: String,
+    pub theme: ThemeConfig,
     #[serde(default = "default_speed")]
     pub speed: u64,
     #[serde(default = "default_background")]
@@ -19,10 +27,156 @@ pub struct Config {
     pub ignore_patterns: Vec<String>,
     #[serde(default)]
     pub speed_rules: Vec<String>,
+    #[serde(default)]
+    pub smart_edit: bool,
+    #[serde(default = "default_deleted_files")]
+    pub deleted_files: String,
+    #[serde(default)]
+    pub animate_renames: bool,
+    #[serde(default = "default_edit_order")]
+    pub edit_order: String,
+    #[serde(default = "default_diff_view")]
+    pub diff_view: String,
+    #[serde(default)]
+    pub change_markers: bool,
+    #[serde(default = "default_color")]
+    pub color: String,
+    #[serde(default)]
+    pub timing: TimingConfig,
+    #[serde(default)]
+    pub duration: Option<String>,
+    #[serde(default)]
+    pub min_duration: Option<String>,
+    #[serde(default)]
+    pub max_duration: Option<String>,
+    #[serde(default)]
+    pub large_files: LargeFilesConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    /// `[repos."path-glob"]` tables overriding the settings above for matching repositories
+    #[serde(default, skip_serializing)]
+    pub repos: BTreeMap<String, toml::Table>,
+}
+
+/// The `[large_files]` config table: thresholds for fast-forwarding large files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LargeFilesConfig {
+    pub max_blob_size: Option<usize>,
+    pub max_change_lines: Option<usize>,
+    pub full_hunks: Option<usize>,
+    pub max_hunk_lines: Option<usize>,
+}
+
+impl LargeFilesConfig {
+    pub fn limits(&self) -> LargeFileLimits {
+        let defaults = LargeFileLimits::default();
+        LargeFileLimits {
+            max_blob_size: self.max_blob_size.unwrap_or(defaults.max_blob_size),
+            max_change_lines: self.max_change_lines.unwrap_or(defaults.max_change_lines),
+            full_hunks: self.full_hunks.unwrap_or(defaults.full_hunks),
+            max_hunk_lines: self.max_hunk_lines.unwrap_or(defaults.max_hunk_lines),
+        }
+    }
+}
+
+/// `theme = "name"`, or `theme = { light = "...", dark = "..." }` to follow the
+/// terminal background
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ThemeConfig {
+    Name(String),
+    Auto(AutoTheme),
+}
+
+impl ThemeConfig {
+    fn to_toml(&self) -> toml_edit::Value {
+        match self {
+            Self::Name(name) => name.as_str().into(),
+            Self::Auto(auto) => {
+                let mut table = toml_edit::InlineTable::new();
+                table.insert("light", auto.light.as_str().into());
+                table.insert("dark", auto.dark.as_str().into());
+                if let Some(hours) = &auto.light_hours {
+                    table.insert("light_hours", hours.as_str().into());
+                }
+                table.into()
+            }
+        }
+    }
+}
+
+/// A light and a dark theme, switched between commits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AutoTheme {
+    pub light: String,
+    pub dark: String,
+    /// Local time range for the light theme, e.g. "07:00-19:00", instead of asking the terminal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub light_hours: Option<String>,
 }
 
-fn default_theme() -> String {
-    "tokyo-night".to_string()
+/// The `[layout]` config table: sizes and visibility of the panes around the editor
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayoutConfig {
+    pub sidebar_width: Option<u16>,
+    pub file_tree_height: Option<u16>,
+    pub editor_height: Option<u16>,
+    pub file_tree: Option<bool>,
+    pub commit_info: Option<bool>,
+    pub terminal: Option<bool>,
+    pub editor_only: Option<bool>,
+}
+
+impl LayoutConfig {
+    pub fn layout(&self) -> Result<PaneLayout> {
+        let defaults = PaneLayout::default();
+        let percent = |key: &str, value: Option<u16>, default: u16| {
+            let value = value.unwrap_or(default);
+            if !(1..=99).contains(&value) {
+                anyhow::bail!(
+                    "Invalid {}: {} (expected a percentage from 1 to 99)",
+                    key,
+                    value
+                );
+            }
+            Ok(value)
+        };
+        let shown = |value: Option<bool>| value.unwrap_or(true) && self.editor_only != Some(true);
+
+        Ok(PaneLayout {
+            sidebar_width: percent("sidebar_width", self.sidebar_width, defaults.sidebar_width)?,
+            file_tree_height: percent(
+                "file_tree_height",
+                self.file_tree_height,
+                defaults.file_tree_height,
+            )?,
+            editor_height: percent("editor_height", self.editor_height, defaults.editor_height)?,
+            file_tree: shown(self.file_tree),
+            commit_info: shown(self.commit_info),
+            terminal: shown(self.terminal),
+        })
+    }
+}
+
+/// Overwrite `base` with the values in `overrides`, merging nested tables key by key
+fn merge_table(base: &mut toml::Table, overrides: &toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => {
+                merge_table(base, overrides)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn default_theme() -> ThemeConfig {
+    ThemeConfig::Name("tokyo-night".to_string())
 }
 
 fn default_speed() -> u64 {
@@ -45,6 +199,22 @@ fn default_ignore_patterns() -> Vec<String> {
     Vec::new()
 }
 
+fn default_deleted_files() -> String {
+    "skip".to_string()
+}
+
+fn default_edit_order() -> String {
+    "sequential".to_string()
+}
+
+fn default_diff_view() -> String {
+    "unified".to_string()
+}
+
+fn default_color() -> String {
+    "auto".to_string()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -55,6 +225,20 @@ impl Default for Config {
             loop_playback: default_loop(),
             ignore_patterns: default_ignore_patterns(),
             speed_rules: Vec::new(),
+            smart_edit: false,
+            deleted_files: default_deleted_files(),
+            animate_renames: false,
+            edit_order: default_edit_order(),
+            diff_view: default_diff_view(),
+            change_markers: false,
+            color: default_color(),
+            timing: TimingConfig::default(),
+            duration: None,
+            min_duration: None,
+            max_duration: None,
+            large_files: LargeFilesConfig::default(),
+            layout: LayoutConfig::default(),
+            repos: BTreeMap::new(),
         }
     }
 }
@@ -74,6 +258,43 @@ impl Config {
             .with_context(|| format!("Failed to parse config file: {}", config_path.display()))
     }
 
+    /// The settings for a repository, with the `[repos."path-glob"]` tables matching its
+    /// path applied on top. More specific (longer) patterns win over shorter ones.
+    pub fn for_repo(&self, repo_path: &Path) -> Result<Self> {
+        let home = dirs::home_dir();
+        let mut matching = Vec::new();
+        for (pattern, table) in &self.repos {
+            let expanded = match (pattern.strip_prefix("~/"), &home) {
+                (Some(rest), Some(home)) => format!("{}/{}", home.display(), rest),
+                _ if pattern.starts_with('/') => pattern.clone(),
+                // A bare name like "gitlogue" or "work/*" matches anywhere
+                _ => format!("**/{}", pattern),
+            };
+            let glob = GlobBuilder::new(expanded.trim_end_matches('/'))
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid repos pattern in config file: {}", pattern))?
+                .compile_matcher();
+            if glob.is_match(repo_path) {
+                matching.push((pattern, table));
+            }
+        }
+        if matching.is_empty() {
+            return Ok(self.clone());
+        }
+        matching.sort_by_key(|(pattern, _)| pattern.len());
+
+        let mut merged = toml::Table::try_from(self).context("Failed to serialize config")?;
+        for (_, table) in matching {
+            merge_table(&mut merged, table);
+        }
+        let mut config: Self = merged
+            .try_into()
+            .with_context(|| format!("Invalid [repos] settings for {}", repo_path.display()))?;
+        config.repos = self.repos.clone();
+        Ok(config)
+    }
+
     pub fn save(&self) -> Result<()> {
         let config_path = Self::config_path()?;
 
@@ -90,7 +311,7 @@ impl Config {
                 })?;
 
             // Update values while preserving comments
-            doc["theme"] = toml_edit::value(self.theme.as_str());
+            doc["theme"] = toml_edit::Item::Value(self.theme.to_toml());
             doc["speed"] = toml_edit::value(self.speed as i64);
             doc["background"] = toml_edit::value(self.background);
             doc["order"] = toml_edit::value(self.order.as_str());
@@ -110,6 +331,14 @@ impl Config {
             }
             doc["speed_rules"] = toml_edit::value(speed_array);
 
+            doc["smart_edit"] = toml_edit::value(self.smart_edit);
+            doc["deleted_files"] = toml_edit::value(self.deleted_files.as_str());
+            doc["animate_renames"] = toml_edit::value(self.animate_renames);
+            doc["edit_order"] = toml_edit::value(self.edit_order.as_str());
+            doc["diff_view"] = toml_edit::value(self.diff_view.as_str());
+            doc["change_markers"] = toml_edit::value(self.change_markers);
+            doc["color"] = toml_edit::value(self.color.as_str());
+
             doc.to_string()
         } else {
             // Create new config with comments
@@ -139,8 +368,9 @@ impl Config {
                 "# gitlogue configuration file\n\
                  # All settings are optional and will use defaults if not specified\n\
                  \n\
-                 # Theme to use for syntax highlighting\n\
-                 theme = \"{}\"\n\
+                 # Theme to use for syntax highlighting, or a light and a dark theme\n\
+                 # picked by the terminal background: {{ light = \"solarized-light\", dark = \"tokyo-night\" }}\n\
+                 theme = {}\n\
                  \n\
                  # Typing speed in milliseconds per character\n\
                  speed = {}\n\
@@ -160,14 +390,42 @@ impl Config {
                  \n\
                  # Speed rules for different file types (pattern:milliseconds)\n\
                  # Examples: [\"*.java:50\", \"*.xml:5\", \"*.rs:30\"]\n\
-                 speed_rules = {}\n",
-                self.theme,
+                 speed_rules = {}\n\
+                 \n\
+                 # Simulate editor auto-pairing and auto-indentation while typing\n\
+                 smart_edit = {}\n\
+                 \n\
+                 # How deleted files are shown: skip, select-all, or line-by-line\n\
+                 deleted_files = \"{}\"\n\
+                 \n\
+                 # Animate the edits of renamed files after the mv\n\
+                 animate_renames = {}\n\
+                 \n\
+                 # Editing order: sequential, or organic (bounce between files like real development)\n\
+                 edit_order = \"{}\"\n\
+                 \n\
+                 # Editor layout: unified, or split (old file beside the buffer being typed)\n\
+                 diff_view = \"{}\"\n\
+                 \n\
+                 # Mark added and modified lines in the editor's gutter\n\
+                 change_markers = {}\n\
+                 \n\
+                 # Colors the terminal supports: auto, truecolor, 256, 16, or none\n\
+                 color = \"{}\"\n",
+                self.theme.to_toml(),
                 self.speed,
                 self.background,
                 self.order,
                 self.loop_playback,
                 patterns_str,
-                speed_rules_str
+                speed_rules_str,
+                self.smart_edit,
+                self.deleted_files,
+                self.animate_renames,
+                self.edit_order,
+                self.diff_view,
+                self.change_markers,
+                self.color
             )
         };
 
@@ -175,6 +433,33 @@ impl Config {
             .with_context(|| format!("Failed to write config file: {}", config_path.display()))
     }
 
+    /// Parse the per-commit duration settings
+    pub fn duration_target(&self) -> Result<DurationTarget> {
+        let parse = |key: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(parse_duration)
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("Invalid {} in config file: {}", key, e))
+        };
+
+        Ok(DurationTarget {
+            target: parse("duration", &self.duration)?,
+            min: parse("min_duration", &self.min_duration)?,
+            max: parse("max_duration", &self.max_duration)?,
+        })
+    }
+
+    /// Parse the `color` setting
+    pub fn color_mode(&self) -> Result<ColorMode> {
+        ColorMode::from_str(&self.color, true).map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid color in config file: '{}' (expected auto, truecolor, 256, 16, or none)",
+                self.color
+            )
+        })
+    }
+
     pub fn config_path() -> Result<PathBuf> {
         let config_dir = dirs::home_dir()
             .context("Failed to determine home directory")?
@@ -191,21 +476,116 @@ impl Config {
         Ok(config_dir.join("config.toml"))
     }
 
-    #[allow(dead_code)]
+    /// Directory user theme files are loaded from, which may not exist
     pub fn themes_dir() -> Result<PathBuf> {
-        let config_dir = dirs::home_dir()
+        Ok(dirs::home_dir()
             .context("Failed to determine home directory")?
             .join(".config")
             .join("gitlogue")
-            .join("themes");
+            .join("themes"))
+    }
 
-        fs::create_dir_all(&config_dir).with_context(|| {
+    /// The themes directory, created if it doesn't exist yet
+    pub fn create_themes_dir() -> Result<PathBuf> {
+        let themes_dir = Self::themes_dir()?;
+        fs::create_dir_all(&themes_dir).with_context(|| {
             format!(
                 "Failed to create themes directory: {}",
-                config_dir.display()
+                themes_dir.display()
             )
         })?;
+        Ok(themes_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_is_a_name_or_light_and_dark_themes() {
+        let config: Config = toml::from_str("theme = \"nord\"").unwrap();
+        assert_eq!(config.theme, ThemeConfig::Name("nord".to_string()));
+        assert_eq!(config.theme.to_toml().to_string(), "\"nord\"");
+
+        let config: Config = toml::from_str(
+            "theme = { light = \"solarized-light\", dark = \"nord\", light_hours = \"07:00-19:00\" }",
+        )
+        .unwrap();
+        let ThemeConfig::Auto(auto) = &config.theme else {
+            panic!("expected light and dark themes");
+        };
+        assert_eq!(auto.light, "solarized-light");
+        assert_eq!(auto.light_hours.as_deref(), Some("07:00-19:00"));
+        assert_eq!(
+            config.theme.to_toml().to_string(),
+            "{ light = \"solarized-light\", dark = \"nord\", light_hours = \"07:00-19:00\" }"
+        );
+
+        assert!(toml::from_str::<Config>("theme = { light = \"nord\" }").is_err());
+    }
+
+    #[test]
+    fn test_repos_tables_override_settings_for_matching_repositories() {
+        let config: Config = toml::from_str(
+            r#"
+            theme = "tokyo-night"
+            speed = 30
+
+            [layout]
+            sidebar_width = 30
+
+            [repos."/src/work/*"]
+            theme = "solarized-light"
+            layout = { terminal = false }
+
+            [repos."/src/work/api"]
+            speed = 10
+
+            [repos."dotfiles"]
+            theme = { light = "solarized-light", dark = "nord" }
+            "#,
+        )
+        .unwrap();
+
+        let api = config.for_repo(Path::new("/src/work/api")).unwrap();
+        assert_eq!(api.theme, ThemeConfig::Name("solarized-light".to_string()));
+        assert_eq!(api.speed, 10);
+        assert_eq!(api.layout.sidebar_width, Some(30));
+        assert_eq!(api.layout.terminal, Some(false));
+        assert_eq!(api.repos.len(), 3);
+
+        let nested = config.for_repo(Path::new("/src/work/api/vendor")).unwrap();
+        assert_eq!(nested.theme, ThemeConfig::Name("tokyo-night".to_string()));
+
+        let dotfiles = config.for_repo(Path::new("/home/me/dotfiles")).unwrap();
+        assert!(matches!(dotfiles.theme, ThemeConfig::Auto(_)));
+        assert_eq!(dotfiles.speed, 30);
+
+        let broken: Config = toml::from_str("[repos.\"*\"]\nspeed = \"fast\"").unwrap();
+        assert!(broken.for_repo(Path::new("/src/app")).is_err());
+    }
+
+    #[test]
+    fn test_large_files_rejects_unknown_keys() {
+        let config: Config = toml::from_str("[large_files]\nmax_blob_size = 1024\n").unwrap();
+        assert_eq!(config.large_files.limits().max_blob_size, 1024);
+        assert!(toml::from_str::<Config>("[large_files]\nmax_blob_sise = 1024\n").is_err());
+    }
+
+    #[test]
+    fn test_layout_rejects_unknown_keys() {
+        let config: Config = toml::from_str("[layout]\nsidebar_width = 25\n").unwrap();
+        assert_eq!(config.layout.sidebar_width, Some(25));
+        assert!(toml::from_str::<Config>("[layout]\nsidebar_widht = 25\n").is_err());
+    }
+
+    #[test]
+    fn test_invalid_color_is_an_error() {
+        let config: Config = toml::from_str("color = \"256\"").unwrap();
+        assert_eq!(config.color_mode().unwrap(), ColorMode::Ansi256);
 
-        Ok(config_dir)
+        let config: Config = toml::from_str("color = \"rainbow\"").unwrap();
+        assert!(config.color_mode().is_err());
     }
 }
@@ -5,18 +5,149 @@ use git2::{Commit as Git2Commit, Delta, DiffOptions, Oid, Repository};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use rand::Rng;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::OnceLock;
 
 // Thread-safe global pattern matcher for user-defined ignore patterns
 static USER_PATTERNS: OnceLock<GlobSet> = OnceLock::new();
 
-// Maximum blob size to read (500KB)
-const MAX_BLOB_SIZE: usize = 500 * 1024;
+// Thread-safe global thresholds for large file handling
+static LARGE_FILE_LIMITS: OnceLock<LargeFileLimits> = OnceLock::new();
+
+/// Thresholds that decide how much of a large file is animated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LargeFileLimits {
+    /// Files larger than this many bytes are fast-forwarded like files with many changed
+    /// lines, without syntax highlighting
+    pub max_blob_size: usize,
+    /// Files with more changed lines are fast-forwarded after the first `full_hunks` hunks
+    pub max_change_lines: usize,
+    /// Number of hunks animated in full before fast-forwarding a large file
+    pub full_hunks: usize,
+    /// Hunks with more changed lines are pasted in one step
+    pub max_hunk_lines: usize,
+}
+
+impl Default for LargeFileLimits {
+    fn default() -> Self {
+        Self {
+            max_blob_size: 500 * 1024,
+            max_change_lines: 2000,
+            full_hunks: 3,
+            max_hunk_lines: 500,
+        }
+    }
+}
 
-// Maximum number of changed lines per file to animate
-// Files with more changes will be skipped to prevent performance issues
-const MAX_CHANGE_LINES: usize = 2000;
+/// Files more than this many times `max_blob_size` are excluded without being read
+const READ_LIMIT_FACTOR: usize = 20;
+
+impl LargeFileLimits {
+    /// Files larger than this many bytes are not read, so they can't exhaust memory
+    pub fn max_read_size(&self) -> usize {
+        self.max_blob_size.saturating_mul(READ_LIMIT_FACTOR)
+    }
+}
+
+/// Initialize large file thresholds (call once at startup)
+pub fn init_large_file_limits(limits: LargeFileLimits) -> Result<()> {
+    LARGE_FILE_LIMITS
+        .set(limits)
+        .map_err(|_| anyhow::anyhow!("Large file limits already initialized"))
+}
+
+/// Get the configured large file thresholds
+pub fn large_file_limits() -> LargeFileLimits {
+    LARGE_FILE_LIMITS.get().copied().unwrap_or_default()
+}
+
+/// Read a blob as text, unless it is binary or too large to read
+fn blob_text(blob: &git2::Blob) -> Option<String> {
+    let readable = !blob.is_binary() && blob.size() <= large_file_limits().max_read_size();
+    readable.then(|| String::from_utf8_lossy(blob.content()).to_string())
+}
+
+/// Size of one side of a diff, looked up in the object database when the diff didn't
+/// load the file
+fn diff_file_size(repo: &Repository, file: &git2::DiffFile) -> u64 {
+    if file.size() > 0 || file.id().is_zero() {
+        return file.size();
+    }
+    repo.odb()
+        .and_then(|odb| odb.read_header(file.id()))
+        .map_or(0, |(size, _)| size as u64)
+}
+
+/// Decode a diff line, without its line ending
+fn line_text(content: &[u8]) -> String {
+    let text = String::from_utf8_lossy(content);
+    text.strip_suffix('\n')
+        .map(|t| t.strip_suffix('\r').unwrap_or(t))
+        .unwrap_or(&text)
+        .to_string()
+}
+
+/// Hunks of a patch with the old and new line number of each line
+fn patch_hunks(patch: &git2::Patch) -> Vec<DiffHunk> {
+    let mut hunks = Vec::new();
+    for hunk_idx in 0..patch.num_hunks() {
+        if let Ok((hunk, _hunk_lines)) = patch.hunk(hunk_idx) {
+            let mut lines = Vec::new();
+            let num_lines = patch.num_lines_in_hunk(hunk_idx).unwrap_or(0);
+
+            let mut old_line_no = hunk.old_start() as usize;
+            let mut new_line_no = hunk.new_start() as usize;
+
+            for line_idx in 0..num_lines {
+                if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
+                    let origin = line.origin();
+                    // "\ No newline at end of file" markers are not file lines
+                    if matches!(origin, '=' | '>' | '<') {
+                        continue;
+                    }
+                    let content = line_text(line.content());
+
+                    let (change_type, old_no, new_no) = match origin {
+                        '+' => {
+                            let no = new_line_no;
+                            new_line_no += 1;
+                            (LineChangeType::Addition, None, Some(no))
+                        }
+                        '-' => {
+                            let no = old_line_no;
+                            old_line_no += 1;
+                            (LineChangeType::Deletion, Some(no), None)
+                        }
+                        _ => {
+                            let old_no = old_line_no;
+                            let new_no = new_line_no;
+                            old_line_no += 1;
+                            new_line_no += 1;
+                            (LineChangeType::Context, Some(old_no), Some(new_no))
+                        }
+                    };
+
+                    lines.push(LineChange {
+                        change_type,
+                        content,
+                        old_line_no: old_no,
+                        new_line_no: new_no,
+                    });
+                }
+            }
+
+            hunks.push(DiffHunk {
+                old_start: hunk.old_start() as usize,
+                old_lines: hunk.old_lines() as usize,
+                new_start: hunk.new_start() as usize,
+                new_lines: hunk.new_lines() as usize,
+                lines,
+            });
+        }
+    }
+    hunks
+}
 
 /// Specifies which working tree changes to show in diff mode
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
@@ -130,6 +261,41 @@ pub fn should_exclude_file(path: &str) -> bool {
     false
 }
 
+/// Determine why a file is not animated, if it isn't; `size` is the larger of the old and
+/// new file sizes
+fn exclusion_reason(
+    path: &str,
+    status: &FileStatus,
+    is_binary: bool,
+    size: u64,
+    old_content: &Option<String>,
+    new_content: &Option<String>,
+) -> Option<String> {
+    if should_exclude_file(path) {
+        return Some("lock/generated file".to_string());
+    }
+
+    let missing_old =
+        matches!(status, FileStatus::Deleted | FileStatus::Modified) && old_content.is_none();
+    let missing_new = matches!(
+        status,
+        FileStatus::Added | FileStatus::Modified | FileStatus::Renamed | FileStatus::Copied
+    ) && new_content.is_none();
+    if !is_binary && (missing_old || missing_new) {
+        let too_large = size > large_file_limits().max_read_size() as u64;
+        return Some(
+            if too_large {
+                "file too large"
+            } else {
+                "file unreadable"
+            }
+            .to_string(),
+        );
+    }
+
+    None
+}
+
 // Check if a commit matches the author filter pattern (case-insensitive partial match)
 fn matches_author(commit: &Git2Commit, pattern: &str) -> bool {
     let author = commit.author();
@@ -180,6 +346,10 @@ pub struct GitRepository {
     // These modes are mutually exclusive based on CLI arguments.
     commit_index: RefCell<usize>,
     commit_range: RefCell<Option<Vec<Oid>>>,
+    // Short names of the branches and tags pointing at each commit, built on first use
+    ref_names: RefCell<Option<HashMap<Oid, Vec<String>>>>,
+    // Checked signatures, so replayed commits don't run `git` again
+    signatures: RefCell<HashMap<Oid, SignatureStatus>>,
     author_filter: Option<String>,
     before_filter: Option<DateTime<Utc>>,
     after_filter: Option<DateTime<Utc>>,
@@ -222,7 +392,7 @@ impl From<Delta> for FileStatus {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum LineChangeType {
     Addition,
     Deletion,
@@ -233,7 +403,6 @@ pub enum LineChangeType {
 pub struct LineChange {
     pub change_type: LineChangeType,
     pub content: String,
-    #[allow(dead_code)]
     pub old_line_no: Option<usize>,
     #[allow(dead_code)]
     pub new_line_no: Option<usize>,
@@ -269,16 +438,113 @@ pub struct FileChange {
     pub diff: String,
 }
 
+/// Whether a commit is signed, and whether its signature checks out
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SignatureStatus {
+    #[default]
+    Unsigned,
+    /// Signed, but the signature could not be checked (e.g. unknown key or no gpg)
+    Unverified,
+    Good,
+    /// Valid when made, but the signature or its key has since expired
+    Expired,
+    Bad,
+}
+
+/// Message trailers that credit people besides the author
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CreditTrailer {
+    CoAuthoredBy,
+    ReviewedBy,
+}
+
+impl CreditTrailer {
+    /// Parses a `Co-authored-by` or `Reviewed-by` line into its kind and the person's name
+    pub fn parse(line: &str) -> Option<(Self, &str)> {
+        let (key, value) = line.trim().split_once(':')?;
+        let kind = if key.eq_ignore_ascii_case("Co-authored-by") {
+            Self::CoAuthoredBy
+        } else if key.eq_ignore_ascii_case("Reviewed-by") {
+            Self::ReviewedBy
+        } else {
+            return None;
+        };
+        // Drop the email address
+        let name = value.split('<').next().unwrap_or_default().trim();
+        (!name.is_empty()).then_some((kind, name))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CommitMetadata {
     pub hash: String,
     pub author: String,
+    /// Committer name, when someone other than the author committed
+    pub committer: Option<String>,
+    /// Names from `Co-authored-by` trailers
+    pub co_authors: Vec<String>,
+    /// Names from `Reviewed-by` trailers
+    pub reviewers: Vec<String>,
+    /// Branches and tags pointing at the commit
+    pub refs: Vec<String>,
+    pub signature: SignatureStatus,
     pub date: DateTime<Utc>,
     pub message: String,
     pub changes: Vec<FileChange>,
 }
 
+impl FileChange {
+    /// Diff two versions of a file that isn't in a repository; `None` for a side
+    /// makes the file added or deleted
+    pub fn from_contents(path: &str, old: Option<&str>, new: Option<&str>) -> Result<Self> {
+        let status = match (old, new) {
+            (None, _) => FileStatus::Added,
+            (_, None) => FileStatus::Deleted,
+            _ => FileStatus::Modified,
+        };
+        let file_path = Path::new(path);
+        let mut patch = git2::Patch::from_buffers(
+            old.unwrap_or_default().as_bytes(),
+            old.map(|_| file_path),
+            new.unwrap_or_default().as_bytes(),
+            new.map(|_| file_path),
+            None,
+        )
+        .with_context(|| format!("Failed to diff {}", path))?;
+        let diff = patch
+            .to_buf()
+            .map(|buf| String::from_utf8_lossy(buf.as_ref()).to_string())
+            .unwrap_or_default();
+
+        Ok(Self {
+            path: path.to_string(),
+            old_path: None,
+            status,
+            is_binary: false,
+            is_excluded: false,
+            exclusion_reason: None,
+            old_content: old.map(String::from),
+            new_content: new.map(String::from),
+            hunks: patch_hunks(&patch),
+            diff,
+        })
+    }
+}
+
 impl CommitMetadata {
+    /// Total added and deleted lines across all changed files
+    pub fn line_totals(&self) -> (usize, usize) {
+        self.changes
+            .iter()
+            .flat_map(|change| &change.hunks)
+            .flat_map(|hunk| &hunk.lines)
+            .fold((0, 0), |(added, deleted), line| match line.change_type {
+                LineChangeType::Addition => (added + 1, deleted),
+                LineChangeType::Deletion => (added, deleted + 1),
+                LineChangeType::Context => (added, deleted),
+            })
+    }
+
     /// Returns indices sorted in FileTree display order (directory -> filename)
     pub fn sorted_file_indices(&self) -> Vec<usize> {
         let mut indices: Vec<usize> = (0..self.changes.len()).collect();
@@ -308,6 +574,8 @@ impl GitRepository {
             commit_cache: RefCell::new(None),
             commit_index: RefCell::new(0),
             commit_range: RefCell::new(None),
+            ref_names: RefCell::new(None),
+            signatures: RefCell::new(HashMap::new()),
             author_filter: None,
             before_filter: None,
             after_filter: None,
@@ -322,7 +590,7 @@ impl GitRepository {
 
         let commit = obj.peel_to_commit().context("Object is not a commit")?;
 
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.extract_metadata_with_changes(&commit)
     }
 
     pub fn random_commit(&self) -> Result<CommitMetadata> {
@@ -336,7 +604,7 @@ impl GitRepository {
             .context("Failed to select random commit")?;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.extract_metadata_with_changes(&commit)
     }
 
     pub fn next_asc_commit(&self) -> Result<CommitMetadata> {
@@ -363,7 +631,7 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.extract_metadata_with_changes(&commit)
     }
 
     pub fn next_desc_commit(&self) -> Result<CommitMetadata> {
@@ -387,7 +655,7 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.extract_metadata_with_changes(&commit)
     }
 
     pub fn reset_index(&self) {
@@ -406,6 +674,28 @@ impl GitRepository {
         self.after_filter = after;
     }
 
+    /// Commit ids for a revision, a range (`A..B`), or the whole history, oldest first
+    pub fn commit_ids(&self, spec: Option<&str>) -> Result<Vec<Oid>> {
+        match spec {
+            Some(range) if range.contains("..") => self.parse_commit_range(range),
+            Some(rev) => {
+                let commit = self
+                    .repo
+                    .revparse_single(rev)
+                    .context("Invalid commit hash or commit not found")?
+                    .peel_to_commit()
+                    .context("Object is not a commit")?;
+                Ok(vec![commit.id()])
+            }
+            None => {
+                self.populate_cache()?;
+                let mut ids = self.commit_cache.borrow().clone().unwrap_or_default();
+                ids.reverse();
+                Ok(ids)
+            }
+        }
+    }
+
     pub fn set_commit_range(&self, range: &str) -> Result<()> {
         let commits = self.parse_commit_range(range)?;
         *self.commit_range.borrow_mut() = Some(commits);
@@ -430,7 +720,7 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.extract_metadata_with_changes(&commit)
     }
 
     pub fn next_range_commit_desc(&self) -> Result<CommitMetadata> {
@@ -452,7 +742,7 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.extract_metadata_with_changes(&commit)
     }
 
     pub fn random_range_commit(&self) -> Result<CommitMetadata> {
@@ -468,7 +758,7 @@ impl GitRepository {
             .context("Failed to select random commit")?;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.extract_metadata_with_changes(&commit)
     }
 
     // Collect non-merge commits from a revwalk, applying author and date filters if set
@@ -567,10 +857,7 @@ impl GitRepository {
         Ok(())
     }
 
-    fn extract_metadata_with_changes(
-        repo: &Repository,
-        commit: &Git2Commit,
-    ) -> Result<CommitMetadata> {
+    fn extract_metadata_with_changes(&self, commit: &Git2Commit) -> Result<CommitMetadata> {
         let hash = commit.id().to_string();
         let author = commit.author();
         let author_name = author.name().unwrap_or("Unknown").to_string();
@@ -578,17 +865,90 @@ impl GitRepository {
         let date = DateTime::from_timestamp(timestamp, 0).unwrap_or_else(Utc::now);
         let message = commit.message().unwrap_or("").trim().to_string();
 
-        let changes = Self::extract_changes(repo, commit)?;
+        let committer = commit.committer();
+        let committer = (committer.name_bytes() != author.name_bytes()
+            || committer.email_bytes() != author.email_bytes())
+        .then(|| committer.name().unwrap_or("Unknown").to_string());
+
+        let mut co_authors = Vec::new();
+        let mut reviewers = Vec::new();
+        for line in message.lines().skip(1) {
+            match CreditTrailer::parse(line) {
+                Some((CreditTrailer::CoAuthoredBy, name)) => co_authors.push(name.to_string()),
+                Some((CreditTrailer::ReviewedBy, name)) => reviewers.push(name.to_string()),
+                None => {}
+            }
+        }
+
+        let changes = Self::extract_changes(&self.repo, commit)?;
 
         Ok(CommitMetadata {
             hash,
             author: author_name,
+            committer,
+            co_authors,
+            reviewers,
+            refs: self.commit_refs(commit.id()),
+            signature: self.signature_status(commit.id()),
             date,
             message,
             changes,
         })
     }
 
+    /// Short names of the branches and tags that point at a commit
+    fn commit_refs(&self, oid: Oid) -> Vec<String> {
+        let mut ref_names = self.ref_names.borrow_mut();
+        let ref_names = ref_names.get_or_insert_with(|| {
+            let mut names: HashMap<Oid, Vec<String>> = HashMap::new();
+            let Ok(references) = self.repo.references() else {
+                return names;
+            };
+            for reference in references
+                .flatten()
+                .filter(|r| r.is_branch() || r.is_remote() || r.is_tag())
+                // Skip symbolic refs such as origin/HEAD
+                .filter(|r| r.symbolic_target().is_none())
+            {
+                if let (Ok(commit), Some(name)) =
+                    (reference.peel_to_commit(), reference.shorthand())
+                {
+                    names.entry(commit.id()).or_default().push(name.to_string());
+                }
+            }
+            names
+        });
+        ref_names.get(&oid).cloned().unwrap_or_default()
+    }
+
+    /// Checks a commit's signature with `git`, which knows the user's gpg and ssh setup
+    fn signature_status(&self, oid: Oid) -> SignatureStatus {
+        if let Some(status) = self.signatures.borrow().get(&oid) {
+            return *status;
+        }
+        let status = if self.repo.extract_signature(&oid, None).is_err() {
+            SignatureStatus::Unsigned
+        } else {
+            let output = std::process::Command::new("git")
+                .arg("--git-dir")
+                .arg(self.repo.path())
+                .args(["log", "-1", "--format=%G?"])
+                .arg(oid.to_string())
+                .output();
+            match output.ok().and_then(|o| o.stdout.first().copied()) {
+                // Good, possibly with unknown validity
+                Some(b'G' | b'U') => SignatureStatus::Good,
+                // Good, but the signature or its key has expired
+                Some(b'X' | b'Y') => SignatureStatus::Expired,
+                // Bad, or made with a revoked key
+                Some(b'B' | b'R') => SignatureStatus::Bad,
+                _ => SignatureStatus::Unverified,
+            }
+        };
+        self.signatures.borrow_mut().insert(oid, status);
+        status
+    }
+
     fn extract_changes(repo: &Repository, commit: &Git2Commit) -> Result<Vec<FileChange>> {
         let commit_tree = commit.tree().context("Failed to get commit tree")?;
         let parent_tree = if commit.parent_count() > 0 {
@@ -639,6 +999,10 @@ impl GitRepository {
             };
 
             let is_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
+            let size = diff_file_size(repo, &delta.old_file())
+                .max(diff_file_size(repo, &delta.new_file()));
+            // Files over the read limit are excluded without loading them for a patch
+            let too_large = size > large_file_limits().max_read_size() as u64;
 
             let old_content = if let Some(parent_tree) = parent_tree.as_ref() {
                 if let Some(old_file_path) = delta.old_file().path() {
@@ -646,13 +1010,7 @@ impl GitRepository {
                         .get_path(old_file_path)
                         .ok()
                         .and_then(|entry| repo.find_blob(entry.id()).ok())
-                        .and_then(|blob| {
-                            if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-                                Some(String::from_utf8_lossy(blob.content()).to_string())
-                            } else {
-                                None
-                            }
-                        })
+                        .and_then(|blob| blob_text(&blob))
                 } else {
                     None
                 }
@@ -665,13 +1023,7 @@ impl GitRepository {
                     .get_path(new_file_path)
                     .ok()
                     .and_then(|entry| repo.find_blob(entry.id()).ok())
-                    .and_then(|blob| {
-                        if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-                            Some(String::from_utf8_lossy(blob.content()).to_string())
-                        } else {
-                            None
-                        }
-                    })
+                    .and_then(|blob| blob_text(&blob))
             } else {
                 None
             };
@@ -679,85 +1031,25 @@ impl GitRepository {
             let mut hunks = Vec::new();
             let mut diff_text = String::new();
 
-            if let Ok(Some(mut patch)) = git2::Patch::from_diff(&diff, i) {
+            let patch = if too_large {
+                None
+            } else {
+                git2::Patch::from_diff(&diff, i).ok().flatten()
+            };
+            if let Some(mut patch) = patch {
                 if let Ok(patch_str) = patch.to_buf() {
                     diff_text = String::from_utf8_lossy(patch_str.as_ref()).to_string();
                 }
 
                 if !is_binary {
-                    for hunk_idx in 0..patch.num_hunks() {
-                        if let Ok((hunk, _hunk_lines)) = patch.hunk(hunk_idx) {
-                            let mut lines = Vec::new();
-                            let num_lines = patch.num_lines_in_hunk(hunk_idx).unwrap_or(0);
-
-                            let mut old_line_no = hunk.old_start() as usize;
-                            let mut new_line_no = hunk.new_start() as usize;
-
-                            for line_idx in 0..num_lines {
-                                if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
-                                    let content =
-                                        String::from_utf8_lossy(line.content()).to_string();
-                                    let origin = line.origin();
-
-                                    let (change_type, old_no, new_no) = match origin {
-                                        '+' => {
-                                            let no = new_line_no;
-                                            new_line_no += 1;
-                                            (LineChangeType::Addition, None, Some(no))
-                                        }
-                                        '-' => {
-                                            let no = old_line_no;
-                                            old_line_no += 1;
-                                            (LineChangeType::Deletion, Some(no), None)
-                                        }
-                                        _ => {
-                                            let old_no = old_line_no;
-                                            let new_no = new_line_no;
-                                            old_line_no += 1;
-                                            new_line_no += 1;
-                                            (LineChangeType::Context, Some(old_no), Some(new_no))
-                                        }
-                                    };
-
-                                    lines.push(LineChange {
-                                        change_type,
-                                        content,
-                                        old_line_no: old_no,
-                                        new_line_no: new_no,
-                                    });
-                                }
-                            }
-
-                            hunks.push(DiffHunk {
-                                old_start: hunk.old_start() as usize,
-                                old_lines: hunk.old_lines() as usize,
-                                new_start: hunk.new_start() as usize,
-                                new_lines: hunk.new_lines() as usize,
-                                lines,
-                            });
-                        }
-                    }
+                    hunks = patch_hunks(&patch);
                 }
             }
 
-            // Calculate total changed lines (additions + deletions)
-            let total_changed_lines: usize = hunks
-                .iter()
-                .flat_map(|hunk| &hunk.lines)
-                .filter(|line| !matches!(line.change_type, LineChangeType::Context))
-                .count();
-
             // Determine exclusion reason
-            let (is_excluded, exclusion_reason) = if should_exclude_file(&path) {
-                (true, Some("lock/generated file".to_string()))
-            } else if total_changed_lines > MAX_CHANGE_LINES {
-                (
-                    true,
-                    Some(format!("too many changes ({} lines)", total_changed_lines)),
-                )
-            } else {
-                (false, None)
-            };
+            let exclusion_reason =
+                exclusion_reason(&path, &status, is_binary, size, &old_content, &new_content);
+            let is_excluded = exclusion_reason.is_some();
 
             changes.push(FileChange {
                 path,
@@ -794,6 +1086,11 @@ impl GitRepository {
         Ok(CommitMetadata {
             hash: "working-tree".to_string(),
             author: "Working Tree".to_string(),
+            committer: None,
+            co_authors: Vec::new(),
+            reviewers: Vec::new(),
+            refs: Vec::new(),
+            signature: SignatureStatus::Unsigned,
             date: Utc::now(),
             message: message.to_string(),
             changes,
@@ -913,27 +1210,21 @@ impl GitRepository {
             };
 
             let is_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
+            let size = diff_file_size(&self.repo, &delta.old_file())
+                .max(diff_file_size(&self.repo, &delta.new_file()));
             let (old_content, new_content) = get_content(&delta);
-            let (hunks, diff_text) = self.extract_hunks_from_diff(diff, i, is_binary)?;
-
-            // Calculate total changed lines
-            let total_changed_lines: usize = hunks
-                .iter()
-                .flat_map(|hunk| &hunk.lines)
-                .filter(|line| !matches!(line.change_type, LineChangeType::Context))
-                .count();
-
-            let (is_excluded, exclusion_reason) = if should_exclude_file(&path) {
-                (true, Some("lock/generated file".to_string()))
-            } else if total_changed_lines > MAX_CHANGE_LINES {
-                (
-                    true,
-                    Some(format!("too many changes ({} lines)", total_changed_lines)),
-                )
+            // Files over the read limit are excluded without loading them for a patch
+            let (hunks, diff_text) = if size > large_file_limits().max_read_size() as u64 {
+                (Vec::new(), String::new())
             } else {
-                (false, None)
+                self.extract_hunks_from_diff(diff, i, is_binary)?
             };
 
+            // Determine exclusion reason
+            let exclusion_reason =
+                exclusion_reason(&path, &status, is_binary, size, &old_content, &new_content);
+            let is_excluded = exclusion_reason.is_some();
+
             changes.push(FileChange {
                 path,
                 old_path,
@@ -960,11 +1251,7 @@ impl GitRepository {
         let path = path?;
         let entry = tree.get_path(path).ok()?;
         let blob = self.repo.find_blob(entry.id()).ok()?;
-        if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-            Some(String::from_utf8_lossy(blob.content()).to_string())
-        } else {
-            None
-        }
+        blob_text(&blob)
     }
 
     /// Extract hunks from a diff at given delta index
@@ -983,57 +1270,7 @@ impl GitRepository {
             }
 
             if !is_binary {
-                for hunk_idx in 0..patch.num_hunks() {
-                    if let Ok((hunk, _hunk_lines)) = patch.hunk(hunk_idx) {
-                        let mut lines = Vec::new();
-                        let num_lines = patch.num_lines_in_hunk(hunk_idx).unwrap_or(0);
-
-                        let mut old_line_no = hunk.old_start() as usize;
-                        let mut new_line_no = hunk.new_start() as usize;
-
-                        for line_idx in 0..num_lines {
-                            if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
-                                let content = String::from_utf8_lossy(line.content()).to_string();
-                                let origin = line.origin();
-
-                                let (change_type, old_no, new_no) = match origin {
-                                    '+' => {
-                                        let no = new_line_no;
-                                        new_line_no += 1;
-                                        (LineChangeType::Addition, None, Some(no))
-                                    }
-                                    '-' => {
-                                        let no = old_line_no;
-                                        old_line_no += 1;
-                                        (LineChangeType::Deletion, Some(no), None)
-                                    }
-                                    _ => {
-                                        let old_no = old_line_no;
-                                        let new_no = new_line_no;
-                                        old_line_no += 1;
-                                        new_line_no += 1;
-                                        (LineChangeType::Context, Some(old_no), Some(new_no))
-                                    }
-                                };
-
-                                lines.push(LineChange {
-                                    change_type,
-                                    content,
-                                    old_line_no: old_no,
-                                    new_line_no: new_no,
-                                });
-                            }
-                        }
-
-                        hunks.push(DiffHunk {
-                            old_start: hunk.old_start() as usize,
-                            old_lines: hunk.old_lines() as usize,
-                            new_start: hunk.new_start() as usize,
-                            new_lines: hunk.new_lines() as usize,
-                            lines,
-                        });
-                    }
-                }
+                hunks = patch_hunks(&patch);
             }
         }
 
@@ -1057,11 +1294,7 @@ impl GitRepository {
         let entry = index.get_path(path, 0)?;
         let blob = self.repo.find_blob(entry.id).ok()?;
 
-        if !blob.is_binary() && blob.size() <= MAX_BLOB_SIZE {
-            Some(String::from_utf8_lossy(blob.content()).to_string())
-        } else {
-            None
-        }
+        blob_text(&blob)
     }
 
     /// Get file content from working directory.
@@ -1069,17 +1302,16 @@ impl GitRepository {
     /// Returns `None` if:
     /// - Path is not provided
     /// - Repository is bare (no working directory)
-    /// - File cannot be read (missing, permissions, binary/non-UTF8)
-    /// - File size exceeds MAX_BLOB_SIZE (500KB)
+    /// - File is larger than the read limit
+    /// - File cannot be read (missing, permissions)
     fn get_workdir_content(&self, path: Option<&std::path::Path>) -> Option<String> {
-        let path = path?;
-        let workdir = self.repo.workdir()?;
-        let full_path = workdir.join(path);
-
-        match std::fs::read_to_string(&full_path) {
-            Ok(content) if content.len() <= MAX_BLOB_SIZE => Some(content),
-            _ => None,
+        let path = self.repo.workdir()?.join(path?);
+        let size = std::fs::metadata(&path).ok()?.len();
+        if size > large_file_limits().max_read_size() as u64 {
+            return None;
         }
+        let bytes = std::fs::read(path).ok()?;
+        Some(String::from_utf8_lossy(&bytes).to_string())
     }
 }
 
@@ -1460,4 +1692,103 @@ mod tests {
         let diff = now.signed_duration_since(result.date);
         assert!(diff.num_seconds() < 60);
     }
+
+    #[test]
+    fn test_diff_lines_exclude_line_endings_and_eof_markers() {
+        let test_repo = TestRepo::new();
+
+        let file_path = test_repo.path.join("test.txt");
+        std::fs::write(&file_path, "a\r\nb").unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index.add_path(std::path::Path::new("test.txt")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = test_repo.repo.find_tree(tree_id).unwrap();
+        let sig = test_repo.repo.signature().unwrap();
+        test_repo
+            .repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+
+        std::fs::write(&file_path, "a\r\nc\r\n").unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let unstaged = repo.get_working_tree_diff(DiffMode::Unstaged).unwrap();
+        let lines: Vec<(LineChangeType, &str)> = unstaged.changes[0].hunks[0]
+            .lines
+            .iter()
+            .map(|l| (l.change_type.clone(), l.content.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (LineChangeType::Context, "a"),
+                (LineChangeType::Deletion, "b"),
+                (LineChangeType::Addition, "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_files_over_read_limit_are_excluded_unread() {
+        let test_repo = TestRepo::new();
+
+        let size = large_file_limits().max_read_size() + 1;
+        let file_path = test_repo.path.join("huge.txt");
+        std::fs::write(&file_path, "x\n".repeat(size / 2 + 1)).unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index.add_path(std::path::Path::new("huge.txt")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = test_repo.repo.find_tree(tree_id).unwrap();
+        let sig = test_repo.repo.signature().unwrap();
+        test_repo
+            .repo
+            .commit(Some("HEAD"), &sig, &sig, "Add huge file", &tree, &[])
+            .unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let change = &repo.get_commit("HEAD").unwrap().changes[0];
+        assert!(change.new_content.is_none());
+        assert_eq!(change.exclusion_reason.as_deref(), Some("file too large"));
+
+        std::fs::write(&file_path, "y\n".repeat(size / 2 + 1)).unwrap();
+        let unstaged = repo.get_working_tree_diff(DiffMode::Unstaged).unwrap();
+        let change = &unstaged.changes[0];
+        assert!(change.old_content.is_none() && change.new_content.is_none());
+        assert_eq!(change.exclusion_reason.as_deref(), Some("file too large"));
+    }
+
+    #[test]
+    fn test_commit_metadata_credits_and_refs() {
+        let test_repo = TestRepo::new();
+
+        let mut index = test_repo.repo.index().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = test_repo.repo.find_tree(tree_id).unwrap();
+        let author = git2::Signature::now("Ada", "ada@example.com").unwrap();
+        let committer = git2::Signature::now("Grace", "grace@example.com").unwrap();
+        let message = "Add parser\n\nCo-authored-by: Linus <linus@example.com>\nreviewed-by: Ken <ken@example.com>\n";
+        let oid = test_repo
+            .repo
+            .commit(Some("HEAD"), &author, &committer, message, &tree, &[])
+            .unwrap();
+        let commit = test_repo.repo.find_commit(oid).unwrap();
+        test_repo
+            .repo
+            .tag_lightweight("v1.0", commit.as_object(), false)
+            .unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit("HEAD").unwrap();
+        assert_eq!(metadata.author, "Ada");
+        assert_eq!(metadata.committer.as_deref(), Some("Grace"));
+        assert_eq!(metadata.co_authors, vec!["Linus"]);
+        assert_eq!(metadata.reviewers, vec!["Ken"]);
+        assert!(metadata.refs.contains(&"v1.0".to_string()));
+        assert_eq!(metadata.signature, SignatureStatus::Unsigned);
+
+        assert_eq!(CreditTrailer::parse("Signed-off-by: Ada <a@b>"), None);
+        assert_eq!(CreditTrailer::parse("Co-authored-by: <a@b>"), None);
+    }
 }
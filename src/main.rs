@@ -1,20 +1,32 @@
 mod animation;
+mod appearance;
 mod config;
 mod git;
 mod panes;
+mod preview;
+mod raster;
+mod record;
+mod svg;
 mod syntax;
 mod theme;
+mod timing;
 mod ui;
 mod widgets;
 
-use animation::SpeedRule;
+use animation::{AnimationEngine, DeletedFileMode, EditOrder, SpeedRule};
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
-use config::Config;
+use config::{Config, LayoutConfig, ThemeConfig};
 use git::{DiffMode, GitRepository};
+use panes::DiffView;
+use raster::{ImageFormat, ImageWriter};
+use record::{CastWriter, FrameSink};
 use std::path::{Path, PathBuf};
-use theme::Theme;
-use ui::UI;
+use std::time::Duration;
+use svg::SvgWriter;
+use theme::{ColorMode, Theme, AA_CONTRAST};
+use timing::{parse_duration, DurationTarget, Timing};
+use ui::{ThemePicker, UI};
 
 /// Defines the order in which commits are played back during animation.
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -74,6 +86,14 @@ pub struct Args {
     )]
     pub background: Option<bool>,
 
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        help = "Colors the terminal supports (overrides config file)"
+    )]
+    pub color: Option<ColorMode>,
+
     #[arg(
         long,
         value_enum,
@@ -91,6 +111,145 @@ pub struct Args {
     )]
     pub loop_playback: Option<bool>,
 
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Simulate editor auto-pairing and auto-indentation while typing (overrides config file)"
+    )]
+    pub smart_edit: Option<bool>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        help = "How deleted files are shown in the editor (overrides config file)"
+    )]
+    pub deleted_files: Option<DeletedFileMode>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Animate the edits of renamed files after the mv (overrides config file)"
+    )]
+    pub animate_renames: Option<bool>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "ORDER",
+        help = "Order in which files and hunks are edited (overrides config file)"
+    )]
+    pub edit_order: Option<EditOrder>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "VIEW",
+        help = "Show the editor as unified, or split with the old file beside it (overrides config file)"
+    )]
+    pub diff_view: Option<DiffView>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Mark added and modified lines in the editor's gutter (overrides config file)"
+    )]
+    pub change_markers: Option<bool>,
+
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        help = "Width of the file tree and commit info column (overrides config file)"
+    )]
+    pub sidebar_width: Option<u16>,
+
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        help = "Height of the file tree above the commit info (overrides config file)"
+    )]
+    pub file_tree_height: Option<u16>,
+
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        help = "Height of the editor above the terminal (overrides config file)"
+    )]
+    pub editor_height: Option<u16>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Show the file tree (use --file-tree=false to hide it, overrides config file)"
+    )]
+    pub file_tree: Option<bool>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Show the commit info (use --commit-info=false to hide it, overrides config file)"
+    )]
+    pub commit_info: Option<bool>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Show the terminal (use --terminal=false to hide it, overrides config file)"
+    )]
+    pub terminal: Option<bool>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        help = "Show only the editor, fullscreen (use --editor-only=false to show every pane, overrides config file)"
+    )]
+    pub editor_only: Option<bool>,
+
+    #[arg(
+        long,
+        value_name = "PRESET|FILE",
+        help = "Timing preset (default, calm, demo, hyper) or timing profile file (overrides config file)"
+    )]
+    pub timing: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help = "Scale the animation speed so every commit takes about this long (e.g., '30s', '1m30s', overrides config file)"
+    )]
+    pub duration: Option<Duration>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help = "Slow down commits that would finish faster than this (overrides config file)"
+    )]
+    pub min_duration: Option<Duration>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help = "Speed up commits that would take longer than this (overrides config file)"
+    )]
+    pub max_duration: Option<Duration>,
+
     #[arg(long, help = "Display third-party license information")]
     pub license: bool,
 
@@ -176,6 +335,14 @@ pub enum Commands {
               help = "Show background colors (use --background=false for transparent)")]
         background: Option<bool>,
 
+        #[arg(
+            long,
+            value_enum,
+            value_name = "MODE",
+            help = "Colors the terminal supports"
+        )]
+        color: Option<ColorMode>,
+
         #[arg(long = "loop", num_args = 0..=1, default_missing_value = "true", value_name = "BOOL",
               help = "Loop the animation continuously")]
         loop_playback: Option<bool>,
@@ -188,6 +355,160 @@ pub enum Commands {
               help = "Set typing speed for files matching pattern (e.g., '*.java:50')")]
         speed_rule: Vec<String>,
     },
+    /// Replay commits headlessly and check that every animated file ends up as committed
+    Verify {
+        #[arg(
+            short,
+            long,
+            value_name = "HASH_OR_RANGE",
+            help = "Verify a specific commit or commit range (defaults to the whole history)"
+        )]
+        commit: Option<String>,
+
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL",
+              help = "Verify with language-aware editing (overrides config file)")]
+        smart_edit: Option<bool>,
+
+        #[arg(
+            long,
+            value_name = "ORDER",
+            help = "Verify with this edit order (overrides config file)"
+        )]
+        edit_order: Option<EditOrder>,
+
+        #[arg(
+            long,
+            value_enum,
+            value_name = "MODE",
+            help = "Verify with this deleted file mode (overrides config file)"
+        )]
+        deleted_files: Option<DeletedFileMode>,
+
+        #[arg(long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL",
+              help = "Verify with rename edits animated (overrides config file)")]
+        animate_renames: Option<bool>,
+
+        #[arg(short = 'i', long = "ignore", value_name = "PATTERN", action = clap::ArgAction::Append,
+              help = "Ignore files matching pattern (gitignore syntax)")]
+        ignore: Vec<String>,
+
+        #[arg(
+            long = "ignore-file",
+            value_name = "PATH",
+            help = "Path to file containing ignore patterns (one per line, like .gitignore)"
+        )]
+        ignore_file: Option<PathBuf>,
+    },
+    /// Export the animation steps of a commit with their timestamps
+    Timeline {
+        #[arg(short, long, value_name = "REV", help = "Commit to export")]
+        commit: String,
+
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FORMAT",
+            default_value = "json",
+            help = "Output format"
+        )]
+        format: TimelineFormat,
+
+        #[arg(
+            short,
+            long,
+            value_name = "MS",
+            help = "Typing speed in milliseconds per character (overrides config file)"
+        )]
+        speed: Option<u64>,
+
+        #[arg(
+            long,
+            value_name = "PRESET|FILE",
+            help = "Pause timings (overrides config file)"
+        )]
+        timing: Option<String>,
+    },
+    /// Record a commit animation to an asciinema cast file, GIF, APNG or SVG
+    Record {
+        #[arg(
+            short,
+            long,
+            value_name = "PATH",
+            help = "File to write, formatted by extension (.cast, .gif, .png, .apng or .svg)"
+        )]
+        output: PathBuf,
+
+        #[arg(
+            short,
+            long,
+            value_name = "REV",
+            default_value = "HEAD",
+            help = "Commit or commit range to record"
+        )]
+        commit: String,
+
+        #[arg(
+            long,
+            value_name = "COLS",
+            default_value_t = 120,
+            help = "Terminal width"
+        )]
+        width: u16,
+
+        #[arg(
+            long,
+            value_name = "ROWS",
+            default_value_t = 36,
+            help = "Terminal height"
+        )]
+        height: u16,
+
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 20,
+            help = "Maximum frames per second for GIF, APNG and SVG output"
+        )]
+        fps: u32,
+
+        #[arg(
+            short,
+            long,
+            value_name = "MS",
+            help = "Typing speed in milliseconds per character (overrides config file)"
+        )]
+        speed: Option<u64>,
+
+        #[arg(
+            short,
+            long,
+            value_name = "NAME",
+            help = "Theme to use (overrides config file)"
+        )]
+        theme: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "PRESET|FILE",
+            help = "Pause timings (overrides config file)"
+        )]
+        timing: Option<String>,
+    },
+}
+
+/// Output formats for `gitlogue timeline`
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum TimelineFormat {
+    #[default]
+    Json,
+}
+
+/// Output formats for `gitlogue record`, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RecordFormat {
+    Cast,
+    Image(ImageFormat),
+    Svg,
 }
 
 #[derive(Subcommand, Debug)]
@@ -199,6 +520,40 @@ pub enum ThemeCommands {
         #[arg(value_name = "NAME", help = "Theme name to set as default")]
         name: String,
     },
+    /// Play a sample commit in each theme, switching with the arrow keys
+    Preview {
+        #[arg(
+            value_name = "NAME",
+            help = "Theme to start with (defaults to the configured theme)"
+        )]
+        name: Option<String>,
+    },
+    /// Convert a VS Code, base16, Alacritty or kitty color scheme into a theme file
+    Import {
+        #[arg(
+            value_name = "FILE",
+            help = "VS Code theme JSON, base16 YAML, or Alacritty/kitty color config"
+        )]
+        file: PathBuf,
+
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Name of the new theme (defaults to the file name)"
+        )]
+        name: Option<String>,
+
+        #[arg(long, help = "Replace an existing theme file with the same name")]
+        force: bool,
+    },
+    /// Report the contrast of every text color on its background
+    Check {
+        #[arg(
+            value_name = "NAME|FILE",
+            help = "Theme name or path to a theme file (defaults to the configured theme)"
+        )]
+        theme: Option<String>,
+    },
 }
 
 impl Args {
@@ -251,6 +606,11 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // User themes live next to the config file
+    if let Ok(dir) = Config::themes_dir() {
+        theme::init_themes_dir(dir).ok();
+    }
+
     // Handle subcommands
     if let Some(ref command) = args.command {
         match command {
@@ -260,6 +620,13 @@ fn main() -> Result<()> {
                     for theme in Theme::available_themes() {
                         println!("  - {}", theme);
                     }
+                    let user_themes = theme::user_themes();
+                    if !user_themes.is_empty() {
+                        println!("\nUser themes ({}):", Config::themes_dir()?.display());
+                        for theme in user_themes {
+                            println!("  - {}", theme);
+                        }
+                    }
                     return Ok(());
                 }
                 ThemeCommands::Set { name } => {
@@ -268,19 +635,158 @@ fn main() -> Result<()> {
 
                     // Load existing config or create new one
                     let mut config = Config::load().unwrap_or_default();
-                    config.theme = name.clone();
+                    config.theme = ThemeConfig::Name(name.clone());
                     config.save()?;
 
                     let config_path = Config::config_path()?;
                     println!("Theme set to '{}' in {}", name, config_path.display());
                     return Ok(());
                 }
+                ThemeCommands::Preview { name } => {
+                    let mut config = Config::load()?;
+                    let start = match name {
+                        Some(name) => name.clone(),
+                        None => config.theme.current()?.to_string(),
+                    };
+                    // Validate theme exists
+                    Theme::load(&start)?;
+                    let color = config.color_mode()?;
+
+                    let mut names: Vec<String> = Theme::available_themes()
+                        .into_iter()
+                        .map(String::from)
+                        .collect();
+                    for user_theme in theme::user_themes() {
+                        if !names.contains(&user_theme) {
+                            names.push(user_theme);
+                        }
+                    }
+                    let themes: Vec<(String, Theme)> = names
+                        .into_iter()
+                        .filter_map(|name| match Theme::load(&name) {
+                            Ok(theme) => {
+                                let mut theme = theme.with_color_mode(color);
+                                if !config.background {
+                                    theme = theme.with_transparent_background();
+                                }
+                                Some((name, theme))
+                            }
+                            Err(e) => {
+                                eprintln!("Warning: Skipping theme '{}': {:#}", name, e);
+                                None
+                            }
+                        })
+                        .collect();
+
+                    let mut ui = UI::new(
+                        config.speed,
+                        None,
+                        Theme::default(),
+                        PlaybackOrder::Asc,
+                        false,
+                        None,
+                        false,
+                        Vec::new(),
+                    );
+                    ui.set_smart_edit(config.smart_edit);
+                    ui.set_diff_view(
+                        DiffView::from_str(&config.diff_view, true).unwrap_or_default(),
+                    );
+                    ui.set_change_markers(config.change_markers);
+                    ui.set_layout(config.layout.layout()?);
+                    ui.set_theme_picker(ThemePicker::new(
+                        themes,
+                        &start,
+                        preview::sample_commit()?,
+                    )?);
+                    ui.run()?;
+
+                    if let Some(name) = ui.chosen_theme() {
+                        config.theme = ThemeConfig::Name(name.to_string());
+                        config.save()?;
+                        println!(
+                            "Theme set to '{}' in {}",
+                            name,
+                            Config::config_path()?.display()
+                        );
+                    }
+                    return Ok(());
+                }
+                ThemeCommands::Import { file, name, force } => {
+                    let imported = theme::import_theme(file)?;
+                    let name = name.as_deref().unwrap_or(&imported.name);
+                    if name.is_empty() || name.contains(['/', '\\']) {
+                        anyhow::bail!("Invalid theme name: {}", name);
+                    }
+
+                    let path = Config::create_themes_dir()?.join(format!("{}.toml", name));
+                    if path.exists() && !force {
+                        anyhow::bail!(
+                            "Theme file already exists: {} (use --force to replace it)",
+                            path.display()
+                        );
+                    }
+                    std::fs::write(&path, imported.to_toml())
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+                    println!(
+                        "Imported {} theme '{}' to {}",
+                        imported.format,
+                        name,
+                        path.display()
+                    );
+                    println!("Use it with: gitlogue --theme {}", name);
+                    return Ok(());
+                }
+                ThemeCommands::Check { theme } => {
+                    let name = match theme {
+                        Some(theme) => theme.clone(),
+                        None => Config::load()?.theme.current()?.to_string(),
+                    };
+                    let path = Path::new(&name);
+                    let loaded = if path.extension().is_some_and(|ext| ext == "toml") {
+                        Theme::load_file(path)?
+                    } else {
+                        Theme::load(&name)?
+                    };
+
+                    let contrast = loaded.contrast();
+                    println!(
+                        "Contrast of {} (WCAG ratio, {} or more meets AA for text):\n",
+                        name, AA_CONTRAST
+                    );
+                    for pair in &contrast {
+                        let note = if !pair.is_readable() {
+                            "  unreadable"
+                        } else if pair.ratio < AA_CONTRAST {
+                            "  low"
+                        } else {
+                            ""
+                        };
+                        println!(
+                            "  {:<30} on {:<26} {:>5.2}{}",
+                            pair.foreground, pair.background, pair.ratio, note
+                        );
+                    }
+
+                    let unreadable = contrast.iter().filter(|pair| !pair.is_readable()).count();
+                    if unreadable > 0 {
+                        anyhow::bail!(
+                            "{} of {} color combinations are unreadable",
+                            unreadable,
+                            contrast.len()
+                        );
+                    }
+                    println!("\nAll {} color combinations are readable", contrast.len());
+                    return Ok(());
+                }
             },
             Commands::Diff {
                 unstaged,
                 speed,
                 theme,
                 background,
+                color,
                 loop_playback,
                 ignore,
                 speed_rule,
@@ -294,6 +800,13 @@ fn main() -> Result<()> {
                     DiffMode::Staged
                 };
 
+                let config = Config::load()?.for_repo(&repo_path)?;
+
+                let mut patterns = config.ignore_patterns.clone();
+                patterns.extend(ignore.clone());
+                git::init_ignore_patterns(&patterns).ok();
+                git::init_large_file_limits(config.large_files.limits()).ok();
+
                 let metadata = repo.get_working_tree_diff(mode)?;
 
                 if metadata.changes.is_empty() {
@@ -301,21 +814,20 @@ fn main() -> Result<()> {
                     return Ok(());
                 }
 
-                let config = Config::load()?;
-
-                let mut patterns = config.ignore_patterns.clone();
-                patterns.extend(ignore.clone());
-                git::init_ignore_patterns(&patterns).ok();
-
-                let theme_name = theme.as_deref().unwrap_or(&config.theme);
                 let speed = speed.unwrap_or(config.speed);
                 let background = background.unwrap_or(config.background);
+                let timing = config.timing.resolve()?;
+                let duration_target = config.duration_target()?;
+                duration_target.validate()?;
                 let loop_playback = loop_playback.unwrap_or(false);
 
-                let mut theme = Theme::load(theme_name)?;
-                if !background {
-                    theme = theme.with_transparent_background();
-                }
+                let color = match color {
+                    Some(color) => *color,
+                    None => config.color_mode()?,
+                };
+
+                let (theme, theme_switcher) =
+                    appearance::load_theme(theme.as_deref(), &config.theme, color, background)?;
 
                 let speed_rules: Vec<SpeedRule> = speed_rule
                     .iter()
@@ -340,12 +852,264 @@ fn main() -> Result<()> {
                     false,
                     speed_rules,
                 );
+                ui.set_smart_edit(config.smart_edit);
+                ui.set_deleted_file_mode(
+                    DeletedFileMode::from_str(&config.deleted_files, true).unwrap_or_default(),
+                );
+                ui.set_animate_renames(config.animate_renames);
+                ui.set_edit_order(
+                    EditOrder::from_str(&config.edit_order, true).unwrap_or_default(),
+                );
+                ui.set_diff_view(DiffView::from_str(&config.diff_view, true).unwrap_or_default());
+                ui.set_change_markers(config.change_markers);
+                ui.set_layout(config.layout.layout()?);
+                ui.set_timing(timing);
+                ui.set_duration_target(duration_target);
                 ui.set_diff_mode(Some(mode));
+                ui.set_theme_switcher(theme_switcher);
                 ui.load_commit(metadata);
                 ui.run()?;
 
                 return Ok(());
             }
+            Commands::Verify {
+                commit,
+                smart_edit,
+                edit_order,
+                deleted_files,
+                animate_renames,
+                ignore,
+                ignore_file,
+            } => {
+                let repo_path = args.validate()?;
+                let repo = GitRepository::open(&repo_path)?;
+
+                let config = Config::load()?.for_repo(&repo_path)?;
+                git::init_ignore_patterns(&ignore_patterns(
+                    &config,
+                    ignore_file.as_deref(),
+                    ignore,
+                ))
+                .ok();
+                git::init_large_file_limits(config.large_files.limits()).ok();
+
+                let mut engine = headless_engine(&config, config.speed)?;
+                if let Some(smart_edit) = smart_edit {
+                    engine.set_smart_edit(*smart_edit);
+                }
+                if let Some(edit_order) = edit_order {
+                    engine.set_edit_order(*edit_order);
+                }
+                if let Some(deleted_files) = deleted_files {
+                    engine.set_deleted_file_mode(*deleted_files);
+                }
+                if let Some(animate_renames) = animate_renames {
+                    engine.set_animate_renames(*animate_renames);
+                }
+
+                let ids = repo.commit_ids(commit.as_deref())?;
+                let mut diverged = 0;
+                for id in &ids {
+                    let metadata = repo.get_commit(&id.to_string())?;
+                    for mismatch in engine.verify_commit(&metadata) {
+                        diverged += 1;
+                        let show = |line: Option<String>| {
+                            line.map_or("<end of file>".to_string(), |l| format!("{:?}", l))
+                        };
+                        println!(
+                            "{} {}:{}",
+                            &metadata.hash[..7],
+                            mismatch.path,
+                            mismatch.line
+                        );
+                        println!("  expected: {}", show(mismatch.expected));
+                        println!("  actual:   {}", show(mismatch.actual));
+                    }
+                }
+
+                if diverged > 0 {
+                    anyhow::bail!(
+                        "{} file{} diverged from the committed content",
+                        diverged,
+                        if diverged == 1 { "" } else { "s" }
+                    );
+                }
+                println!(
+                    "Verified {} commit{}: every animated file matches its committed content",
+                    ids.len(),
+                    if ids.len() == 1 { "" } else { "s" }
+                );
+                return Ok(());
+            }
+            Commands::Record {
+                output,
+                commit,
+                width,
+                height,
+                fps,
+                speed,
+                theme,
+                timing,
+            } => {
+                let repo_path = args.validate()?;
+                let repo = GitRepository::open(&repo_path)?;
+
+                let config = Config::load()?.for_repo(&repo_path)?;
+                git::init_ignore_patterns(&config.ignore_patterns).ok();
+                git::init_large_file_limits(config.large_files.limits()).ok();
+
+                let extension = output
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .map(|e| e.to_ascii_lowercase());
+                let format = match extension.as_deref() {
+                    Some("cast") => RecordFormat::Cast,
+                    Some("gif") => RecordFormat::Image(ImageFormat::Gif),
+                    Some("png" | "apng") => RecordFormat::Image(ImageFormat::Apng),
+                    Some("svg") => RecordFormat::Svg,
+                    _ => anyhow::bail!(
+                        "Unsupported output format: {} (use .cast, .gif, .png, .apng or .svg)",
+                        output.display()
+                    ),
+                };
+
+                let theme_name = match theme {
+                    Some(name) => name.as_str(),
+                    None => config.theme.current()?,
+                };
+                let speed = speed.unwrap_or(config.speed);
+                let timing = match timing.as_deref() {
+                    Some(value) => Timing::from_arg(value)?,
+                    None => config.timing.resolve()?,
+                };
+                let duration_target = config.duration_target()?;
+                duration_target.validate()?;
+
+                // Images have no terminal background to show through
+                let mut theme = Theme::load(theme_name)?;
+                if !config.background && format == RecordFormat::Cast {
+                    theme = theme.with_transparent_background();
+                }
+
+                let speed_rules: Vec<SpeedRule> = config
+                    .speed_rules
+                    .iter()
+                    .filter_map(|s| {
+                        SpeedRule::parse(s).or_else(|| {
+                            eprintln!("Warning: Invalid speed rule '{}', skipping", s);
+                            None
+                        })
+                    })
+                    .collect();
+
+                let ids = repo.commit_ids(Some(commit))?;
+                let Some(first) = ids.first() else {
+                    anyhow::bail!("No commits to record");
+                };
+                let first = repo.get_commit(&first.to_string())?;
+                let title = format!(
+                    "gitlogue: {} {}",
+                    &first.hash[..7],
+                    first.message.lines().next().unwrap_or_default()
+                );
+
+                let mut sink: Box<dyn FrameSink> = match format {
+                    RecordFormat::Cast => {
+                        Box::new(CastWriter::create(output, *width, *height, &title)?)
+                    }
+                    RecordFormat::Image(format) => Box::new(ImageWriter::create(
+                        output, format, *width, *height, *fps, &theme,
+                    )?),
+                    RecordFormat::Svg => {
+                        Box::new(SvgWriter::create(output, *width, *height, *fps, &theme)?)
+                    }
+                };
+
+                let mut ui = UI::new(
+                    speed,
+                    None,
+                    theme,
+                    PlaybackOrder::Asc,
+                    false,
+                    Some(commit.clone()),
+                    ids.len() > 1,
+                    speed_rules,
+                );
+                ui.set_smart_edit(config.smart_edit);
+                ui.set_deleted_file_mode(
+                    DeletedFileMode::from_str(&config.deleted_files, true).unwrap_or_default(),
+                );
+                ui.set_animate_renames(config.animate_renames);
+                ui.set_edit_order(
+                    EditOrder::from_str(&config.edit_order, true).unwrap_or_default(),
+                );
+                ui.set_diff_view(DiffView::from_str(&config.diff_view, true).unwrap_or_default());
+                ui.set_change_markers(config.change_markers);
+                ui.set_layout(config.layout.layout()?);
+                ui.set_timing(timing);
+                ui.set_duration_target(duration_target);
+
+                let mut time_ms = 0;
+                for (i, id) in ids.iter().enumerate() {
+                    let metadata = if i == 0 {
+                        first.clone()
+                    } else {
+                        time_ms += ui.idle_ms();
+                        repo.get_commit(&id.to_string())?
+                    };
+                    ui.load_commit(metadata);
+                    time_ms = ui.record(sink.as_mut(), *width, *height, time_ms)?;
+                }
+                sink.finish(time_ms)?;
+
+                println!(
+                    "Recorded {} commit{} to {}",
+                    ids.len(),
+                    if ids.len() == 1 { "" } else { "s" },
+                    output.display()
+                );
+                return Ok(());
+            }
+            Commands::Timeline {
+                commit,
+                format,
+                speed,
+                timing,
+            } => {
+                let repo_path = args.validate()?;
+                let repo = GitRepository::open(&repo_path)?;
+
+                let config = Config::load()?.for_repo(&repo_path)?;
+                git::init_ignore_patterns(&config.ignore_patterns).ok();
+                git::init_large_file_limits(config.large_files.limits()).ok();
+
+                let speed = speed.unwrap_or(config.speed);
+                let mut engine = headless_engine(&config, speed)?;
+                if let Some(value) = timing {
+                    engine.set_timing(Timing::from_arg(value)?);
+                }
+
+                let metadata = repo.get_commit(commit)?;
+                engine.load_commit(&metadata);
+
+                match format {
+                    TimelineFormat::Json => {
+                        let timeline = serde_json::json!({
+                            "commit": {
+                                "hash": metadata.hash,
+                                "author": metadata.author,
+                                "date": metadata.date.to_rfc3339(),
+                                "message": metadata.message,
+                            },
+                            "speed_ms": speed,
+                            "duration_ms": engine.estimate_duration_ms(),
+                            "steps": engine.timeline(),
+                        });
+                        println!("{}", serde_json::to_string_pretty(&timeline)?);
+                    }
+                }
+                return Ok(());
+            }
         }
     }
 
@@ -376,23 +1140,15 @@ fn main() -> Result<()> {
     let is_filtered = args.author.is_some() || args.before.is_some() || args.after.is_some();
 
     // Load config: CLI arguments > config file > defaults
-    let config = Config::load()?;
+    let config = Config::load()?.for_repo(&repo_path)?;
 
-    // Initialize ignore patterns: CLI flags > ignore-file > config
-    let mut patterns = config.ignore_patterns.clone();
-    if let Some(path) = &args.ignore_file {
-        if let Ok(content) = std::fs::read_to_string(path) {
-            patterns.extend(
-                content
-                    .lines()
-                    .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
-                    .map(String::from),
-            );
-        }
-    }
-    patterns.extend(args.ignore.clone());
-    git::init_ignore_patterns(&patterns).ok();
-    let theme_name = args.theme.as_deref().unwrap_or(&config.theme);
+    git::init_ignore_patterns(&ignore_patterns(
+        &config,
+        args.ignore_file.as_deref(),
+        &args.ignore,
+    ))
+    .ok();
+    git::init_large_file_limits(config.large_files.limits()).ok();
     let speed = args.speed.unwrap_or(config.speed);
     let background = args.background.unwrap_or(config.background);
     let mut order = args.order.unwrap_or(match config.order.as_str() {
@@ -407,12 +1163,33 @@ fn main() -> Result<()> {
     }
 
     let loop_playback = args.loop_playback.unwrap_or(config.loop_playback);
-    let mut theme = Theme::load(theme_name)?;
-
-    // Apply transparent background if requested
-    if !background {
-        theme = theme.with_transparent_background();
+    let timing = match args.timing.as_deref() {
+        Some(value) => Timing::from_arg(value)?,
+        None => config.timing.resolve()?,
+    };
+    let config_duration = config.duration_target()?;
+    let duration_target = DurationTarget {
+        target: args.duration.or(config_duration.target),
+        min: args.min_duration.or(config_duration.min),
+        max: args.max_duration.or(config_duration.max),
+    };
+    duration_target.validate()?;
+    let layout = LayoutConfig {
+        sidebar_width: args.sidebar_width.or(config.layout.sidebar_width),
+        file_tree_height: args.file_tree_height.or(config.layout.file_tree_height),
+        editor_height: args.editor_height.or(config.layout.editor_height),
+        file_tree: args.file_tree.or(config.layout.file_tree),
+        commit_info: args.commit_info.or(config.layout.commit_info),
+        terminal: args.terminal.or(config.layout.terminal),
+        editor_only: args.editor_only.or(config.layout.editor_only),
     }
+    .layout()?;
+    let color = match args.color {
+        Some(color) => color,
+        None => config.color_mode()?,
+    };
+    let (theme, theme_switcher) =
+        appearance::load_theme(args.theme.as_deref(), &config.theme, color, background)?;
 
     // Setup commit range if specified
     if is_range_mode {
@@ -468,8 +1245,67 @@ fn main() -> Result<()> {
         is_range_mode,
         speed_rules,
     );
+    ui.set_smart_edit(args.smart_edit.unwrap_or(config.smart_edit));
+    ui.set_deleted_file_mode(args.deleted_files.unwrap_or_else(|| {
+        DeletedFileMode::from_str(&config.deleted_files, true).unwrap_or_default()
+    }));
+    ui.set_animate_renames(args.animate_renames.unwrap_or(config.animate_renames));
+    ui.set_edit_order(
+        args.edit_order
+            .unwrap_or_else(|| EditOrder::from_str(&config.edit_order, true).unwrap_or_default()),
+    );
+    ui.set_diff_view(
+        args.diff_view
+            .unwrap_or_else(|| DiffView::from_str(&config.diff_view, true).unwrap_or_default()),
+    );
+    ui.set_change_markers(args.change_markers.unwrap_or(config.change_markers));
+    ui.set_layout(layout);
+    ui.set_timing(timing);
+    ui.set_duration_target(duration_target);
+    ui.set_theme_switcher(theme_switcher);
     ui.load_commit(metadata);
     ui.run()?;
 
     Ok(())
 }
+
+/// Collects ignore patterns: CLI flags > ignore-file > config
+fn ignore_patterns(config: &Config, ignore_file: Option<&Path>, ignore: &[String]) -> Vec<String> {
+    let mut patterns = config.ignore_patterns.clone();
+    if let Some(path) = ignore_file {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            patterns.extend(
+                content
+                    .lines()
+                    .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+                    .map(String::from),
+            );
+        }
+    }
+    patterns.extend(ignore.iter().cloned());
+    patterns
+}
+
+/// Builds an animation engine for headless subcommands from the config file
+fn headless_engine(config: &Config, speed: u64) -> Result<AnimationEngine> {
+    let duration_target = config.duration_target()?;
+    duration_target.validate()?;
+
+    let mut engine = AnimationEngine::new(speed);
+    engine.set_speed_rules(
+        config
+            .speed_rules
+            .iter()
+            .filter_map(|s| SpeedRule::parse(s))
+            .collect(),
+    );
+    engine.set_smart_edit(config.smart_edit);
+    engine.set_deleted_file_mode(
+        DeletedFileMode::from_str(&config.deleted_files, true).unwrap_or_default(),
+    );
+    engine.set_animate_renames(config.animate_renames);
+    engine.set_edit_order(EditOrder::from_str(&config.edit_order, true).unwrap_or_default());
+    engine.set_timing(config.timing.resolve()?);
+    engine.set_duration_target(duration_target);
+    Ok(engine)
+}
@@ -1,33 +1,156 @@
+use clap::ValueEnum;
 use ratatui::{
-    layout::Rect,
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Padding},
+    widgets::{Block, Padding, Paragraph},
     Frame,
 };
 
-use crate::animation::{ActivePane, AnimationEngine};
+use crate::animation::{ActivePane, AnimationEngine, LineMarker, LineOrigin};
+use crate::syntax::TokenType;
 use crate::theme::Theme;
 use crate::widgets::SelectableParagraph;
 
-pub struct EditorPane;
+/// How the editor pane presents the file being edited
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum DiffView {
+    /// The buffer being typed only
+    #[default]
+    Unified,
+    /// The old file on the left, the buffer being typed on the right
+    Split,
+}
+
+#[derive(Default)]
+pub struct EditorPane {
+    view: DiffView,
+    change_markers: bool,
+}
 
 struct HighlightContext<'a> {
     line_content: &'a str,
-    line_num: usize,
     show_cursor: bool,
     cursor_col: usize,
-    cursor_line: usize,
+    origin: LineOrigin,
     old_highlights: &'a [crate::syntax::HighlightSpan],
     new_highlights: &'a [crate::syntax::HighlightSpan],
     old_line_offsets: &'a [usize],
     new_line_offsets: &'a [usize],
-    line_offset: isize,
+    language: Option<&'a str>,
     theme: &'a Theme,
 }
 
 impl EditorPane {
+    pub fn set_view(&mut self, view: DiffView) {
+        self.view = view;
+    }
+
+    /// Mark added and modified lines in the gutter and tint their background
+    pub fn set_change_markers(&mut self, enabled: bool) {
+        self.change_markers = enabled;
+    }
+
     pub fn render(&self, f: &mut Frame, area: Rect, engine: &AnimationEngine, theme: &Theme) {
+        match self.view {
+            DiffView::Unified => self.render_buffer(f, area, engine, theme),
+            DiffView::Split => {
+                let layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Percentage(50), // Old file
+                        Constraint::Length(1),      // Vertical separator
+                        Constraint::Fill(1),        // Buffer being typed
+                    ])
+                    .split(area);
+
+                self.render_old_file(f, layout[0], engine, theme);
+
+                let separator: Vec<Line> = (0..layout[1].height).map(|_| Line::from("│")).collect();
+                f.render_widget(
+                    Paragraph::new(separator).style(
+                        Style::default()
+                            .fg(theme.separator)
+                            .bg(theme.background_right),
+                    ),
+                    layout[1],
+                );
+
+                self.render_buffer(f, layout[2], engine, theme);
+            }
+        }
+    }
+
+    /// Old content of the current file, scrolled along with the buffer, with the lines
+    /// the change deletes highlighted
+    fn render_old_file(&self, f: &mut Frame, area: Rect, engine: &AnimationEngine, theme: &Theme) {
+        let block = Block::default()
+            .style(Style::default().bg(theme.background_right))
+            .padding(Padding::vertical(1));
+
+        let content_height = area.height.saturating_sub(2) as usize; // Subtract top and bottom padding
+        let buffer = &engine.buffer;
+        let scroll_offset = buffer.old_scroll_offset();
+        let old_lines = &buffer.old_content_lines;
+        let line_num_width = format!("{}", old_lines.len()).len().max(3);
+        let deleted = engine.deleted_old_lines();
+
+        let visible_lines: Vec<Line> = old_lines
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(content_height)
+            .map(|(line_num, line_content)| {
+                let line_num_str = format!("{:>width$} ", line_num + 1, width = line_num_width);
+                let mut spans = Vec::new();
+                if deleted.contains(&line_num) {
+                    let style = Style::default().fg(theme.file_tree_deleted);
+                    spans.push(Span::styled(line_num_str, style));
+                    spans.push(Span::styled("- ", style));
+                    spans.push(Span::styled(line_content.as_str(), style));
+                } else {
+                    spans.push(Span::styled(
+                        line_num_str,
+                        Style::default().fg(theme.editor_line_number),
+                    ));
+                    spans.push(Span::styled(
+                        "  ",
+                        Style::default().fg(theme.editor_separator),
+                    ));
+                    spans.extend(self.highlight_line(HighlightContext {
+                        line_content,
+                        show_cursor: false,
+                        cursor_col: 0,
+                        origin: LineOrigin::Old(line_num),
+                        old_highlights: &buffer.old_highlights,
+                        new_highlights: &buffer.new_highlights,
+                        old_line_offsets: &buffer.old_content_line_offsets,
+                        new_line_offsets: &buffer.new_content_line_offsets,
+                        language: engine.highlighter.borrow().language_name(),
+                        theme,
+                    }));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        // Keep the row facing the cursor line selected, like the buffer side
+        let selected_line_index = buffer
+            .cursor_line
+            .checked_sub(buffer.scroll_offset)
+            .filter(|&idx| idx < visible_lines.len());
+
+        let content = SelectableParagraph::new(visible_lines)
+            .block(block)
+            .selected_line(selected_line_index)
+            .selected_style(Theme::highlight(theme.editor_cursor_line_bg))
+            .background_style(Style::default().bg(theme.background_right))
+            .padding(Padding::horizontal(2))
+            .dim(20, 0.6);
+        f.render_widget(content, area);
+    }
+
+    fn render_buffer(&self, f: &mut Frame, area: Rect, engine: &AnimationEngine, theme: &Theme) {
         let block = Block::default()
             .style(Style::default().bg(theme.background_right))
             .padding(Padding::vertical(1));
@@ -63,7 +186,7 @@ impl EditorPane {
         let content = SelectableParagraph::new(visible_lines)
             .block(block)
             .selected_line(selected_line_index)
-            .selected_style(Style::default().bg(theme.editor_cursor_line_bg))
+            .selected_style(Theme::highlight(theme.editor_cursor_line_bg))
             .background_style(Style::default().bg(theme.background_right))
             .padding(Padding::horizontal(2))
             .dim(20, 0.6);
@@ -85,31 +208,57 @@ impl EditorPane {
 
         spans.push(self.render_line_number(line_num, is_cursor_line, line_num_width, theme));
 
-        spans.push(Span::styled(
-            "  ",
-            Style::default().fg(theme.editor_separator),
-        ));
+        let marker = engine
+            .buffer
+            .line_markers
+            .get(line_num)
+            .copied()
+            .flatten()
+            .filter(|_| self.change_markers);
+        let (gutter, line_bg) = match marker {
+            Some(LineMarker::Added) => (
+                Span::styled("▎ ", Style::default().fg(theme.editor_gutter_added)),
+                Some(theme.editor_line_added_bg),
+            ),
+            Some(LineMarker::Modified) => (
+                Span::styled("▎ ", Style::default().fg(theme.editor_gutter_modified)),
+                Some(theme.editor_line_modified_bg),
+            ),
+            None => (
+                Span::styled("  ", Style::default().fg(theme.editor_separator)),
+                None,
+            ),
+        };
+        spans.push(gutter);
 
         let show_cursor =
             is_cursor_line && engine.cursor_visible && engine.active_pane == ActivePane::Editor;
 
         let line_spans = self.highlight_line(HighlightContext {
             line_content,
-            line_num,
             show_cursor,
             cursor_col: engine.buffer.cursor_col,
-            cursor_line: engine.buffer.cursor_line,
+            origin: engine
+                .buffer
+                .line_origins
+                .get(line_num)
+                .copied()
+                .unwrap_or(LineOrigin::Old(line_num)),
             old_highlights: &engine.buffer.old_highlights,
             new_highlights: &engine.buffer.new_highlights,
             old_line_offsets: &engine.buffer.old_content_line_offsets,
             new_line_offsets: &engine.buffer.new_content_line_offsets,
-            line_offset: engine.line_offset,
+            language: engine.highlighter.borrow().language_name(),
             theme,
         });
 
         spans.extend(line_spans);
 
-        Line::from(spans)
+        let line = Line::from(spans);
+        match line_bg {
+            Some(bg) => line.style(Style::default().bg(bg)),
+            None => line,
+        }
     }
 
     fn render_line_number(
@@ -134,21 +283,15 @@ impl EditorPane {
     }
 
     fn highlight_line(&self, ctx: HighlightContext<'_>) -> Vec<Span<'_>> {
-        let (highlights, line_offsets) = self.select_highlights_and_offsets(
-            ctx.line_num,
-            ctx.cursor_line,
-            ctx.old_highlights,
-            ctx.new_highlights,
-            ctx.old_line_offsets,
-            ctx.new_line_offsets,
-        );
-
-        let byte_offset = self.calculate_byte_offset(
-            ctx.line_num,
-            ctx.cursor_line,
-            ctx.line_offset,
-            line_offsets,
-        );
+        let (highlights, line_offsets, source_line) = match ctx.origin {
+            LineOrigin::Old(line) => (ctx.old_highlights, ctx.old_line_offsets, line),
+            LineOrigin::New(line) => (ctx.new_highlights, ctx.new_line_offsets, line),
+        };
+
+        let byte_offset = line_offsets
+            .get(source_line)
+            .copied()
+            .unwrap_or_else(|| *line_offsets.last().unwrap_or(&0));
 
         let line_highlights =
             self.filter_line_highlights(highlights, byte_offset, ctx.line_content.len());
@@ -156,41 +299,6 @@ impl EditorPane {
         self.apply_highlights(&line_highlights, byte_offset, &ctx)
     }
 
-    fn select_highlights_and_offsets<'a>(
-        &self,
-        line_num: usize,
-        cursor_line: usize,
-        old_highlights: &'a [crate::syntax::HighlightSpan],
-        new_highlights: &'a [crate::syntax::HighlightSpan],
-        old_line_offsets: &'a [usize],
-        new_line_offsets: &'a [usize],
-    ) -> (&'a [crate::syntax::HighlightSpan], &'a [usize]) {
-        if line_num <= cursor_line {
-            (new_highlights, new_line_offsets)
-        } else {
-            (old_highlights, old_line_offsets)
-        }
-    }
-
-    fn calculate_byte_offset(
-        &self,
-        line_num: usize,
-        cursor_line: usize,
-        line_offset: isize,
-        line_offsets: &[usize],
-    ) -> usize {
-        let target_line = if line_num > cursor_line {
-            ((line_num as isize) - line_offset).max(0) as usize
-        } else {
-            line_num
-        };
-
-        line_offsets
-            .get(target_line)
-            .copied()
-            .unwrap_or_else(|| *line_offsets.last().unwrap_or(&0))
-    }
-
     fn filter_line_highlights(
         &self,
         highlights: &[crate::syntax::HighlightSpan],
@@ -225,48 +333,35 @@ impl EditorPane {
             let char_byte_end = char_byte_start + ch.len_utf8();
             relative_byte += ch.len_utf8();
 
-            let color =
-                self.get_char_color(char_byte_start, char_byte_end, line_highlights, ctx.theme);
+            let style = self.get_char_style(char_byte_start, char_byte_end, line_highlights, ctx);
 
             if ctx.show_cursor && char_idx == ctx.cursor_col {
                 // Cursor character - bright highlight
-                spans.push(Span::styled(
-                    ch.to_string(),
-                    Style::default()
-                        .bg(ctx.theme.editor_cursor_char_bg)
-                        .fg(ctx.theme.editor_cursor_char_fg)
-                        .add_modifier(Modifier::BOLD),
-                ));
+                spans.push(Span::styled(ch.to_string(), ctx.theme.cursor_style()));
             } else {
                 // Normal character
-                spans.push(Span::styled(ch.to_string(), Style::default().fg(color)));
+                spans.push(Span::styled(ch.to_string(), style));
             }
         }
 
         if ctx.show_cursor && ctx.cursor_col >= chars.len() {
-            spans.push(Span::styled(
-                " ",
-                Style::default()
-                    .bg(ctx.theme.editor_cursor_char_bg)
-                    .fg(ctx.theme.editor_cursor_char_fg)
-                    .add_modifier(Modifier::BOLD),
-            ));
+            spans.push(Span::styled(" ", ctx.theme.cursor_style()));
         }
 
         spans
     }
 
-    fn get_char_color(
+    fn get_char_style(
         &self,
         char_byte_start: usize,
         char_byte_end: usize,
         line_highlights: &[(usize, usize, crate::syntax::TokenType)],
-        theme: &Theme,
-    ) -> Color {
-        line_highlights
+        ctx: &HighlightContext,
+    ) -> Style {
+        let token_type = line_highlights
             .iter()
             .find(|h| char_byte_start >= h.0 && char_byte_end <= h.1)
-            .map(|h| h.2.color(theme))
-            .unwrap_or(theme.syntax_variable) // Use theme color instead of Color::White
+            .map_or(TokenType::Variable, |h| h.2);
+        token_type.style(ctx.theme, ctx.language)
     }
 }
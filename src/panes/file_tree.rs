@@ -18,6 +18,8 @@ type FileTree = BTreeMap<String, Vec<FileEntry>>;
 pub struct FileTreePane {
     cached_lines: Vec<Line<'static>>,
     cached_current_line_index: Option<usize>,
+    /// File index shown on each line, `None` for directory headers
+    cached_line_files: Vec<Option<usize>>,
     cached_metadata_id: Option<String>,
     cached_current_file_index: Option<usize>,
 }
@@ -27,6 +29,7 @@ impl FileTreePane {
         Self {
             cached_lines: vec![Line::from("No commit loaded")],
             cached_current_line_index: None,
+            cached_line_files: Vec::new(),
             cached_metadata_id: None,
             cached_current_file_index: None,
         }
@@ -47,16 +50,27 @@ impl FileTreePane {
             return;
         }
 
-        let (lines, current_line_index) =
+        let (lines, current_line_index, line_files) =
             Self::build_tree_lines(metadata, current_file_index, theme);
 
         self.cached_lines = lines;
         self.cached_current_line_index = current_line_index;
+        self.cached_line_files = line_files;
         self.cached_metadata_id = Some(metadata_id);
         self.cached_current_file_index = Some(current_file_index);
     }
 
     pub fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        f.render_widget(self.paragraph(theme), area);
+    }
+
+    /// Index of the file shown at screen row `y` when rendered into `area`
+    pub fn file_at(&self, area: Rect, y: u16, theme: &Theme) -> Option<usize> {
+        let line = self.paragraph(theme).line_at(area, y)?;
+        self.cached_line_files.get(line).copied().flatten()
+    }
+
+    fn paragraph(&self, theme: &Theme) -> SelectableParagraph<'static> {
         let block = Block::default()
             .style(Style::default().bg(theme.background_left))
             .padding(Padding {
@@ -66,21 +80,20 @@ impl FileTreePane {
                 bottom: 1,
             });
 
-        let content = SelectableParagraph::new(self.cached_lines.clone())
+        SelectableParagraph::new(self.cached_lines.clone())
             .block(block)
             .selected_line(self.cached_current_line_index)
-            .selected_style(Style::default().bg(theme.file_tree_current_file_bg))
+            .selected_style(Theme::highlight(theme.file_tree_current_file_bg))
             .background_style(Style::default().bg(theme.background_left))
             .padding(Padding::horizontal(2))
-            .dim(20, 0.6);
-        f.render_widget(content, area);
+            .dim(20, 0.6)
     }
 
     fn build_tree_lines(
         metadata: &CommitMetadata,
         current_file_index: usize,
         theme: &Theme,
-    ) -> (Vec<Line<'static>>, Option<usize>) {
+    ) -> (Vec<Line<'static>>, Option<usize>, Vec<Option<usize>>) {
         // Build directory tree
         let mut tree: FileTree = BTreeMap::new();
 
@@ -134,6 +147,7 @@ impl FileTreePane {
 
         let mut lines = Vec::new();
         let mut current_line_index = None;
+        let mut line_files = Vec::new();
         let sorted_dirs: Vec<_> = tree.keys().cloned().collect();
 
         for dir in sorted_dirs {
@@ -151,6 +165,7 @@ impl FileTreePane {
                         .add_modifier(Modifier::BOLD),
                 )];
                 lines.push(Line::from(dir_spans));
+                line_files.push(None);
             }
 
             // Add files
@@ -200,9 +215,10 @@ impl FileTreePane {
                 ];
 
                 lines.push(Line::from(spans));
+                line_files.push(Some(*index));
             }
         }
 
-        (lines, current_line_index)
+        (lines, current_line_index, line_files)
     }
 }
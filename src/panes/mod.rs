@@ -3,7 +3,7 @@ mod file_tree;
 mod status_bar;
 mod terminal;
 
-pub use editor::EditorPane;
+pub use editor::{DiffView, EditorPane};
 pub use file_tree::FileTreePane;
 pub use status_bar::StatusBarPane;
 pub use terminal::TerminalPane;
@@ -1,20 +1,36 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Position, Rect},
     style::Style,
     text::{Line, Span},
     widgets::{Block, Padding},
     Frame,
 };
 
-use crate::git::CommitMetadata;
+use unicode_width::UnicodeWidthStr;
+
+use crate::git::{CommitMetadata, CreditTrailer, SignatureStatus};
 use crate::theme::Theme;
 use crate::widgets::SelectableParagraph;
 
-pub struct StatusBarPane;
+const PADDING_X: u16 = 2;
+const PADDING_Y: u16 = 1;
+const HASH_LABEL: &str = "hash: ";
+
+#[derive(Default)]
+pub struct StatusBarPane {
+    /// Screen area of the commit hash as last rendered, `None` for the working tree
+    hash_area: Option<Rect>,
+}
 
 impl StatusBarPane {
+    /// Whether screen position (`x`, `y`) is on the last rendered commit hash
+    pub fn hash_contains(&self, x: u16, y: u16) -> bool {
+        self.hash_area
+            .is_some_and(|area| area.contains(Position::new(x, y)))
+    }
+
     pub fn render(
-        &self,
+        &mut self,
         f: &mut Frame,
         area: Rect,
         metadata: Option<&CommitMetadata>,
@@ -22,7 +38,8 @@ impl StatusBarPane {
     ) {
         let block = Block::default()
             .style(Style::default().bg(theme.background_left))
-            .padding(Padding::vertical(1));
+            .padding(Padding::vertical(PADDING_Y));
+        self.hash_area = None;
 
         let status_text = if let Some(meta) = metadata {
             let is_working_tree = meta.hash == "working-tree";
@@ -31,17 +48,39 @@ impl StatusBarPane {
             } else {
                 &meta.hash[..7.min(meta.hash.len())]
             };
+            if !is_working_tree {
+                // The hash follows the label on the first line, inside the padding
+                let hash_area = Rect::new(
+                    area.x + PADDING_X + HASH_LABEL.len() as u16,
+                    area.y + PADDING_Y,
+                    hash_display.width() as u16,
+                    1,
+                );
+                self.hash_area = Some(hash_area.intersection(area));
+            }
+
+            let mut lines = vec![Line::from(vec![
+                Span::raw(HASH_LABEL),
+                Span::styled(hash_display, Style::default().fg(theme.status_hash)),
+            ])];
 
-            let mut lines = vec![
-                Line::from(vec![
-                    Span::raw("hash: "),
-                    Span::styled(hash_display, Style::default().fg(theme.status_hash)),
-                ]),
-                Line::from(vec![
-                    Span::raw("author: "),
-                    Span::styled(&meta.author, Style::default().fg(theme.status_author)),
-                ]),
-            ];
+            if !meta.refs.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::raw("refs: "),
+                    Span::styled(meta.refs.join(", "), Style::default().fg(theme.status_hash)),
+                ]));
+            }
+
+            let people = std::iter::once(("author", &meta.author))
+                .chain(meta.committer.iter().map(|name| ("committer", name)))
+                .chain(meta.co_authors.iter().map(|name| ("co-author", name)))
+                .chain(meta.reviewers.iter().map(|name| ("reviewer", name)));
+            for (label, name) in people {
+                lines.push(Line::from(vec![
+                    Span::raw(format!("{}: ", label)),
+                    Span::styled(name.as_str(), Style::default().fg(theme.status_author)),
+                ]));
+            }
 
             // Only show date for actual commits (not working tree)
             if !is_working_tree {
@@ -52,9 +91,42 @@ impl StatusBarPane {
                 ]));
             }
 
-            // Add commit message lines (skip empty lines)
+            let signature = match meta.signature {
+                SignatureStatus::Unsigned => None,
+                SignatureStatus::Unverified => Some(("unverified", theme.status_no_commit)),
+                SignatureStatus::Good => Some(("good", theme.file_tree_added)),
+                SignatureStatus::Expired => Some(("expired", theme.status_no_commit)),
+                SignatureStatus::Bad => Some(("bad", theme.file_tree_deleted)),
+            };
+            if let Some((status, color)) = signature {
+                lines.push(Line::from(vec![
+                    Span::raw("signature: "),
+                    Span::styled(status, Style::default().fg(color)),
+                ]));
+            }
+
+            let (additions, deletions) = meta.line_totals();
+            let files = meta.changes.len();
+            lines.push(Line::from(vec![
+                Span::raw(format!(
+                    "{} {} ",
+                    files,
+                    if files == 1 { "file" } else { "files" }
+                )),
+                Span::styled(
+                    format!("+{}", additions),
+                    Style::default().fg(theme.file_tree_stats_added),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("-{}", deletions),
+                    Style::default().fg(theme.file_tree_stats_deleted),
+                ),
+            ]));
+
+            // Add commit message lines (skip empty lines and the trailers shown above)
             for msg_line in meta.message.lines() {
-                if !msg_line.trim().is_empty() {
+                if !msg_line.trim().is_empty() && CreditTrailer::parse(msg_line).is_none() {
                     lines.push(Line::from(vec![Span::styled(
                         msg_line,
                         Style::default().fg(theme.status_message),
@@ -73,7 +145,7 @@ impl StatusBarPane {
         let content = SelectableParagraph::new(status_text)
             .block(block)
             .background_style(Style::default().bg(theme.background_left))
-            .padding(Padding::horizontal(2));
+            .padding(Padding::horizontal(PADDING_X));
 
         f.render_widget(content, area);
     }
@@ -43,8 +43,7 @@ impl TerminalPane {
                             )];
                             spans.push(Span::styled(
                                 " ",
-                                Style::default()
-                                    .bg(theme.terminal_cursor_bg)
+                                Theme::highlight(theme.terminal_cursor_bg)
                                     .fg(theme.terminal_cursor_fg)
                                     .add_modifier(Modifier::BOLD),
                             ));
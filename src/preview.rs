@@ -0,0 +1,119 @@
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::git::{CommitMetadata, FileChange, SignatureStatus};
+
+/// Hash of the sample commit, which has no repository behind it
+pub const SAMPLE_HASH: &str = "preview";
+
+const INVENTORY_OLD: &str = r#"use std::collections::HashMap;
+
+/// Items kept in stock, by name
+pub struct Inventory {
+    items: HashMap<String, u32>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self {
+            items: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, name: &str, count: u32) {
+        *self.items.entry(name.to_string()).or_insert(0) += count;
+    }
+}
+"#;
+
+const INVENTORY_NEW: &str = r#"use std::collections::HashMap;
+
+const LOW_STOCK: u32 = 5;
+
+/// Items kept in stock, by name
+pub struct Inventory {
+    items: HashMap<String, u32>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self {
+            items: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, name: &str, count: u32) {
+        *self.items.entry(name.to_string()).or_insert(0) += count;
+    }
+
+    /// Take items out of stock, failing if there aren't enough
+    pub fn remove(&mut self, name: &str, count: u32) -> Result<u32, String> {
+        let stock = self.items.get_mut(name).ok_or("unknown item")?;
+        if *stock < count {
+            return Err(format!("only {} {} left", stock, name));
+        }
+        *stock -= count;
+        Ok(*stock)
+    }
+
+    // Names of items that need restocking, lowest stock first
+    pub fn low_stock(&self) -> Vec<&str> {
+        let mut low: Vec<_> = self.items.iter().filter(|(_, &n)| n <= LOW_STOCK).collect();
+        low.sort_by_key(|(_, &n)| n);
+        low.into_iter().map(|(name, _)| name.as_str()).collect()
+    }
+}
+"#;
+
+const TEST_NEW: &str = r#"use inventory::Inventory;
+
+#[test]
+fn removing_too_many_items_fails() {
+    let mut inventory = Inventory::new();
+    inventory.add("apple", 3);
+    assert!(inventory.remove("apple", 4).is_err());
+    assert_eq!(inventory.remove("apple", 2), Ok(1));
+}
+"#;
+
+/// A short commit with a Rust snippet covering the common syntax colors,
+/// played by `gitlogue theme preview`
+pub fn sample_commit() -> Result<CommitMetadata> {
+    Ok(CommitMetadata {
+        hash: SAMPLE_HASH.to_string(),
+        author: "gitlogue".to_string(),
+        committer: None,
+        co_authors: Vec::new(),
+        reviewers: Vec::new(),
+        refs: vec!["main".to_string()],
+        signature: SignatureStatus::Unsigned,
+        date: Utc::now(),
+        message: "Add stock removal and low stock report".to_string(),
+        changes: vec![
+            FileChange::from_contents("src/lib.rs", Some(INVENTORY_OLD), Some(INVENTORY_NEW))?,
+            FileChange::from_contents("tests/inventory.rs", None, Some(TEST_NEW))?,
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{FileStatus, LineChangeType};
+
+    #[test]
+    fn test_sample_commit_diffs_the_snippets() {
+        let commit = sample_commit().unwrap();
+        let [modified, added] = &commit.changes[..] else {
+            panic!("expected two files");
+        };
+
+        assert!(matches!(modified.status, FileStatus::Modified));
+        assert!(matches!(added.status, FileStatus::Added));
+        assert_eq!(commit.line_totals(), (28, 0));
+        assert!(modified.hunks[0]
+            .lines
+            .iter()
+            .any(|line| matches!(line.change_type, LineChangeType::Context)));
+    }
+}
@@ -0,0 +1,472 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_9X18, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, OriginDimensions, Pixel, Point},
+    text::{Baseline, Text},
+    Drawable,
+};
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+use crate::record::{FrameSink, FrameThrottle};
+use crate::theme::Theme;
+
+const CELL_WIDTH: usize = 9;
+const CELL_HEIGHT: usize = 18;
+
+/// Animated image formats the renderer can write
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Gif,
+    Apng,
+}
+
+/// A glyph as a cell-sized mask of lit pixels
+struct Glyph(Vec<bool>);
+
+impl OriginDimensions for Glyph {
+    fn size(&self) -> embedded_graphics::geometry::Size {
+        embedded_graphics::geometry::Size::new(CELL_WIDTH as u32, CELL_HEIGHT as u32)
+    }
+}
+
+impl DrawTarget for Glyph {
+    type Color = BinaryColor;
+    type Error = std::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let (x, y) = (point.x as usize, point.y as usize);
+            if point.x >= 0 && point.y >= 0 && x < CELL_WIDTH && y < CELL_HEIGHT {
+                self.0[y * CELL_WIDTH + x] = color.is_on();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Glyph {
+    /// Rasterizes a character with the bundled bitmap font, drawing box-drawing
+    /// and block characters as lines so panes and separators stay connected
+    fn new(ch: char) -> Self {
+        let mut glyph = Glyph(vec![false; CELL_WIDTH * CELL_HEIGHT]);
+        if let Some((left, right, up, down)) = box_arms(ch) {
+            let (mid_x, mid_y) = (CELL_WIDTH / 2, CELL_HEIGHT / 2);
+            for x in 0..CELL_WIDTH {
+                if (left && x <= mid_x) || (right && x >= mid_x) {
+                    glyph.0[mid_y * CELL_WIDTH + x] = true;
+                }
+            }
+            for y in 0..CELL_HEIGHT {
+                if (up && y <= mid_y) || (down && y >= mid_y) {
+                    glyph.0[y * CELL_WIDTH + mid_x] = true;
+                }
+            }
+        } else if ch == '█' {
+            glyph.0.fill(true);
+        } else if !ch.is_whitespace() {
+            let style = MonoTextStyle::new(&FONT_9X18, BinaryColor::On);
+            let mut text = [0u8; 4];
+            let _ = Text::with_baseline(
+                ch.encode_utf8(&mut text),
+                Point::zero(),
+                style,
+                Baseline::Top,
+            )
+            .draw(&mut glyph);
+        }
+        glyph
+    }
+}
+
+/// Which arms (left, right, up, down) a box-drawing character has
+fn box_arms(ch: char) -> Option<(bool, bool, bool, bool)> {
+    let arms = match ch {
+        '─' | '━' => (true, true, false, false),
+        '│' | '┃' => (false, false, true, true),
+        '┌' | '╭' => (false, true, false, true),
+        '┐' | '╮' => (true, false, false, true),
+        '└' | '╰' => (false, true, true, false),
+        '┘' | '╯' => (true, false, true, false),
+        '├' => (false, true, true, true),
+        '┤' => (true, false, true, true),
+        '┬' => (true, true, false, true),
+        '┴' => (true, true, true, false),
+        '┼' => (true, true, true, true),
+        _ => return None,
+    };
+    Some(arms)
+}
+
+/// RGB value of a terminal color, using the xterm palette for named and indexed colors
+pub fn rgb(color: Color) -> Option<[u8; 3]> {
+    const ANSI: [[u8; 3]; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some([r, g, b]),
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(i) => i,
+    };
+    Some(match index {
+        0..=15 => ANSI[index as usize],
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = index - 16;
+            [level(i / 36), level(i / 6 % 6), level(i % 6)]
+        }
+        _ => [8 + (index - 232) * 10; 3],
+    })
+}
+
+/// Turns ratatui buffers into palette-indexed pixels
+struct Rasterizer {
+    glyphs: HashMap<char, Glyph>,
+    palette: Vec<[u8; 3]>,
+    indices: HashMap<[u8; 3], u8>,
+    default_fg: [u8; 3],
+    default_bg: [u8; 3],
+}
+
+impl Rasterizer {
+    fn new(theme: &Theme) -> Self {
+        Self {
+            glyphs: HashMap::new(),
+            palette: Vec::new(),
+            indices: HashMap::new(),
+            default_fg: rgb(theme.file_tree_default).unwrap_or([229, 229, 229]),
+            default_bg: rgb(theme.background_right).unwrap_or([0, 0, 0]),
+        }
+    }
+
+    /// Palette index of a color; once the palette is full, the closest existing color
+    fn index(&mut self, color: [u8; 3]) -> u8 {
+        if let Some(&index) = self.indices.get(&color) {
+            return index;
+        }
+        let index = if self.palette.len() < 256 {
+            self.palette.push(color);
+            (self.palette.len() - 1) as u8
+        } else {
+            let distance = |c: &[u8; 3]| -> u32 {
+                (0..3)
+                    .map(|i| (c[i] as i32 - color[i] as i32).pow(2) as u32)
+                    .sum()
+            };
+            (0..self.palette.len())
+                .min_by_key(|&i| distance(&self.palette[i]))
+                .unwrap_or(0) as u8
+        };
+        self.indices.insert(color, index);
+        index
+    }
+
+    fn rasterize(&mut self, buffer: &Buffer) -> Vec<u8> {
+        let area = buffer.area;
+        let width = area.width as usize * CELL_WIDTH;
+        let mut pixels = vec![0u8; width * area.height as usize * CELL_HEIGHT];
+
+        for row in 0..area.height {
+            for col in 0..area.width {
+                let cell = &buffer[(area.x + col, area.y + row)];
+                let mut fg = rgb(cell.fg).unwrap_or(self.default_fg);
+                let mut bg = rgb(cell.bg).unwrap_or(self.default_bg);
+                if cell.modifier.contains(Modifier::REVERSED) {
+                    std::mem::swap(&mut fg, &mut bg);
+                }
+                let (fg, bg) = (self.index(fg), self.index(bg));
+
+                let ch = cell.symbol().chars().next().unwrap_or(' ');
+                let glyph = self.glyphs.entry(ch).or_insert_with(|| Glyph::new(ch));
+                let bold = cell.modifier.contains(Modifier::BOLD);
+                let underline = cell.modifier.contains(Modifier::UNDERLINED);
+
+                let (x0, y0) = (col as usize * CELL_WIDTH, row as usize * CELL_HEIGHT);
+                for y in 0..CELL_HEIGHT {
+                    for x in 0..CELL_WIDTH {
+                        let lit = glyph.0[y * CELL_WIDTH + x]
+                            || (bold && x > 0 && glyph.0[y * CELL_WIDTH + x - 1])
+                            || (underline && y == CELL_HEIGHT - 2);
+                        pixels[(y0 + y) * width + x0 + x] = if lit { fg } else { bg };
+                    }
+                }
+            }
+        }
+        pixels
+    }
+
+    fn flat_palette(&self) -> Vec<u8> {
+        self.palette.iter().flatten().copied().collect()
+    }
+}
+
+/// Frames kept for an APNG, run-length encoded until the palette and frame count are known
+struct ApngFrame {
+    runs: Vec<(u8, u32)>,
+    delay_ms: u64,
+}
+
+enum Output {
+    Gif {
+        encoder: gif::Encoder<BufWriter<File>>,
+        previous: Option<Vec<u8>>,
+    },
+    Apng {
+        out: BufWriter<File>,
+        frames: Vec<ApngFrame>,
+    },
+}
+
+/// Writes rendered frames to an animated GIF or APNG, keeping at most one frame per interval
+pub struct ImageWriter {
+    output: Output,
+    rasterizer: Rasterizer,
+    width: usize,
+    height: usize,
+    throttle: FrameThrottle,
+}
+
+impl ImageWriter {
+    pub fn create(
+        path: &Path,
+        format: ImageFormat,
+        width: u16,
+        height: u16,
+        fps: u32,
+        theme: &Theme,
+    ) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create image file: {}", path.display()))?;
+        let out = BufWriter::new(file);
+        let (pixel_width, pixel_height) =
+            (width as usize * CELL_WIDTH, height as usize * CELL_HEIGHT);
+
+        let output = match format {
+            ImageFormat::Gif => {
+                let mut encoder =
+                    gif::Encoder::new(out, pixel_width as u16, pixel_height as u16, &[])?;
+                encoder.set_repeat(gif::Repeat::Infinite)?;
+                Output::Gif {
+                    encoder,
+                    previous: None,
+                }
+            }
+            ImageFormat::Apng => Output::Apng {
+                out,
+                frames: Vec::new(),
+            },
+        };
+
+        Ok(Self {
+            output,
+            rasterizer: Rasterizer::new(theme),
+            width: pixel_width,
+            height: pixel_height,
+            throttle: FrameThrottle::new(fps),
+        })
+    }
+
+    fn write(&mut self, start_ms: u64, end_ms: u64, buffer: &Buffer) -> Result<()> {
+        let pixels = self.rasterizer.rasterize(buffer);
+        match &mut self.output {
+            Output::Gif { encoder, previous } => {
+                // GIF delays are in centiseconds; round both ends so errors don't accumulate
+                let delay = (end_ms.div_ceil(10) - start_ms.div_ceil(10)).clamp(2, 65535) as u16;
+                let (left, top, width, height) =
+                    changed_region(previous.as_deref(), &pixels, self.width, self.height);
+                let region: Vec<u8> = (top..top + height)
+                    .flat_map(|y| &pixels[y * self.width + left..y * self.width + left + width])
+                    .copied()
+                    .collect();
+                let mut frame = gif::Frame::from_palette_pixels(
+                    width as u16,
+                    height as u16,
+                    region,
+                    self.rasterizer.flat_palette(),
+                    None,
+                );
+                frame.left = left as u16;
+                frame.top = top as u16;
+                frame.delay = delay;
+                frame.dispose = gif::DisposalMethod::Keep;
+                encoder.write_frame(&frame)?;
+                *previous = Some(pixels);
+            }
+            Output::Apng { frames, .. } => {
+                let mut runs: Vec<(u8, u32)> = Vec::new();
+                for &index in &pixels {
+                    match runs.last_mut() {
+                        Some((last, count)) if *last == index => *count += 1,
+                        _ => runs.push((index, 1)),
+                    }
+                }
+                frames.push(ApngFrame {
+                    runs,
+                    delay_ms: end_ms - start_ms,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Bounding box (left, top, width, height) of the pixels that changed, at least 1×1
+fn changed_region(
+    previous: Option<&[u8]>,
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+) -> (usize, usize, usize, usize) {
+    let Some(previous) = previous else {
+        return (0, 0, width, height);
+    };
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (width, height, 0, 0);
+    for (i, (a, b)) in previous.iter().zip(pixels).enumerate() {
+        if a != b {
+            let (x, y) = (i % width, i / width);
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+    }
+    if min_x > max_x {
+        return (0, 0, 1, 1);
+    }
+    (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1)
+}
+
+impl FrameSink for ImageWriter {
+    fn frame(&mut self, time_ms: u64, buffer: &Buffer) -> Result<()> {
+        if let Some((start_ms, end_ms, frame)) = self.throttle.push(time_ms, buffer) {
+            self.write(start_ms, end_ms, &frame)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, time_ms: u64) -> Result<()> {
+        if let Some((start_ms, end_ms, frame)) = self.throttle.flush(time_ms) {
+            self.write(start_ms, end_ms, &frame)?;
+        }
+
+        let palette = self.rasterizer.flat_palette();
+        match &mut self.output {
+            Output::Gif { .. } => {}
+            Output::Apng { out, frames } => {
+                let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+                encoder.set_color(png::ColorType::Indexed);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.set_palette(palette);
+                encoder.set_animated(frames.len() as u32, 0)?;
+                let mut writer = encoder.write_header()?;
+                for frame in frames.iter() {
+                    let delay_ms = frame.delay_ms.min(u64::from(u16::MAX)) as u16;
+                    writer.set_frame_delay(delay_ms, 1000)?;
+                    let pixels: Vec<u8> = frame
+                        .runs
+                        .iter()
+                        .flat_map(|&(index, count)| std::iter::repeat_n(index, count as usize))
+                        .collect();
+                    writer.write_image_data(&pixels)?;
+                }
+                writer.finish()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    #[test]
+    fn test_rasterize_uses_cell_colors() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_string(
+            0,
+            0,
+            "─",
+            Style::default()
+                .fg(Color::Rgb(255, 0, 0))
+                .bg(Color::Rgb(0, 0, 255)),
+        );
+
+        let mut rasterizer = Rasterizer::new(&Theme::default());
+        let pixels = rasterizer.rasterize(&buffer);
+        let width = 2 * CELL_WIDTH;
+        let red = rasterizer.indices[&[255, 0, 0]];
+        let blue = rasterizer.indices[&[0, 0, 255]];
+
+        assert_eq!(pixels.len(), width * CELL_HEIGHT);
+        assert_eq!(pixels[(CELL_HEIGHT / 2) * width], red);
+        assert_eq!(pixels[0], blue);
+        // The unstyled second cell uses the theme background
+        let background = rasterizer.index(rgb(Theme::default().background_right).unwrap());
+        assert_eq!(pixels[CELL_WIDTH], background);
+    }
+
+    #[test]
+    fn test_changed_region_bounds_differences() {
+        let previous = vec![0u8; 16];
+        let mut pixels = previous.clone();
+        pixels[5] = 1;
+        pixels[10] = 1;
+        assert_eq!(changed_region(Some(&previous), &pixels, 4, 4), (1, 1, 2, 2));
+        assert_eq!(
+            changed_region(Some(&previous), &previous, 4, 4),
+            (0, 0, 1, 1)
+        );
+        assert_eq!(changed_region(None, &pixels, 4, 4), (0, 0, 4, 4));
+    }
+
+    #[test]
+    fn test_indexed_colors_follow_xterm_palette() {
+        assert_eq!(rgb(Color::Indexed(196)), Some([255, 0, 0]));
+        assert_eq!(rgb(Color::Indexed(232)), Some([8, 8, 8]));
+        assert_eq!(rgb(Color::Reset), None);
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Destination for frames rendered off-screen
+pub trait FrameSink {
+    /// Records the frame shown from `time_ms` on
+    fn frame(&mut self, time_ms: u64, buffer: &Buffer) -> Result<()>;
+
+    /// Holds the last frame until `time_ms` and completes the file
+    fn finish(&mut self, time_ms: u64) -> Result<()>;
+}
+
+/// Keeps at most one frame per interval for image outputs, showing the latest state
+/// of each interval for as long as the interval lasts
+pub struct FrameThrottle {
+    interval_ms: u64,
+    /// Latest frame and the time it was first shown, not yet written
+    pending: Option<(u64, Buffer)>,
+}
+
+impl FrameThrottle {
+    pub fn new(fps: u32) -> Self {
+        Self {
+            interval_ms: 1000 / u64::from(fps.max(1)),
+            pending: None,
+        }
+    }
+
+    /// Takes the next frame, returning a finished one with its start and end time if any
+    pub fn push(&mut self, time_ms: u64, buffer: &Buffer) -> Option<(u64, u64, Buffer)> {
+        match self.pending.take() {
+            // Too soon after the pending frame: show this state in its place
+            Some((start_ms, _)) if time_ms < start_ms + self.interval_ms => {
+                self.pending = Some((start_ms, buffer.clone()));
+                None
+            }
+            Some((start_ms, pending)) => {
+                self.pending = Some((time_ms, buffer.clone()));
+                Some((start_ms, time_ms, pending))
+            }
+            None => {
+                self.pending = Some((time_ms, buffer.clone()));
+                None
+            }
+        }
+    }
+
+    /// Ends the last frame at `time_ms`
+    pub fn flush(&mut self, time_ms: u64) -> Option<(u64, u64, Buffer)> {
+        self.pending
+            .take()
+            .map(|(start_ms, pending)| (start_ms, time_ms.max(start_ms + 1), pending))
+    }
+}
+
+/// Writes rendered frames to an asciicast v2 file, emitting only the cells that changed
+pub struct CastWriter {
+    out: BufWriter<File>,
+    previous: Buffer,
+}
+
+impl CastWriter {
+    /// Creates the cast file and writes its header
+    pub fn create(path: &Path, width: u16, height: u16, title: &str) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create cast file: {}", path.display()))?;
+        let mut out = BufWriter::new(file);
+
+        let header = serde_json::json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": chrono::Utc::now().timestamp(),
+            "title": title,
+            "env": { "TERM": "xterm-256color" },
+        });
+        writeln!(out, "{}", header)?;
+
+        let mut writer = Self {
+            out,
+            previous: Buffer::empty(Rect::new(0, 0, width, height)),
+        };
+        // Start from a cleared screen with the terminal cursor hidden
+        writer.event(0, "\x1b[?25l\x1b[2J\x1b[H")?;
+        Ok(writer)
+    }
+
+    fn event(&mut self, time_ms: u64, data: &str) -> Result<()> {
+        let event = serde_json::json!([time_ms as f64 / 1000.0, "o", data]);
+        writeln!(self.out, "{}", event)?;
+        Ok(())
+    }
+}
+
+impl FrameSink for CastWriter {
+    /// Skips frames where nothing changed
+    fn frame(&mut self, time_ms: u64, buffer: &Buffer) -> Result<()> {
+        let data = encode_diff(&self.previous, buffer);
+        if !data.is_empty() {
+            self.event(time_ms, &data)?;
+            self.previous = buffer.clone();
+        }
+        Ok(())
+    }
+
+    /// Also restores the terminal style and cursor
+    fn finish(&mut self, time_ms: u64) -> Result<()> {
+        self.event(time_ms, "\x1b[0m\x1b[?25h")?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Encode the cells that differ between two buffers as ANSI escape sequences
+fn encode_diff(previous: &Buffer, next: &Buffer) -> String {
+    let mut data = String::new();
+    let mut cursor: Option<(u16, u16)> = None;
+    let mut style: Option<(Color, Color, Modifier)> = None;
+
+    for (x, y, cell) in previous.diff(next) {
+        if cursor != Some((x, y)) {
+            let _ = write!(data, "\x1b[{};{}H", y + 1, x + 1);
+        }
+        let cell_style = (cell.fg, cell.bg, cell.modifier);
+        if style != Some(cell_style) {
+            data.push_str(&sgr(cell));
+            style = Some(cell_style);
+        }
+        data.push_str(cell.symbol());
+        cursor = Some((x + cell.symbol().width().max(1) as u16, y));
+    }
+
+    if style.is_some() {
+        data.push_str("\x1b[0m");
+    }
+    data
+}
+
+/// Select Graphic Rendition sequence that resets and then applies a cell's style
+fn sgr(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+
+    const MODIFIERS: [(Modifier, &str); 9] = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::RAPID_BLINK, "6"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::HIDDEN, "8"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+    for (modifier, code) in MODIFIERS {
+        if cell.modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+
+    if let Some(code) = color_code(cell.fg, false) {
+        codes.push(code);
+    }
+    if let Some(code) = color_code(cell.bg, true) {
+        codes.push(code);
+    }
+
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// SGR parameters for a foreground or background color, or `None` for the terminal default
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Black => base.to_string(),
+        Color::Red => (base + 1).to_string(),
+        Color::Green => (base + 2).to_string(),
+        Color::Yellow => (base + 3).to_string(),
+        Color::Blue => (base + 4).to_string(),
+        Color::Magenta => (base + 5).to_string(),
+        Color::Cyan => (base + 6).to_string(),
+        Color::Gray => (base + 7).to_string(),
+        Color::DarkGray => (base + 60).to_string(),
+        Color::LightRed => (base + 61).to_string(),
+        Color::LightGreen => (base + 62).to_string(),
+        Color::LightYellow => (base + 63).to_string(),
+        Color::LightBlue => (base + 64).to_string(),
+        Color::LightMagenta => (base + 65).to_string(),
+        Color::LightCyan => (base + 66).to_string(),
+        Color::White => (base + 67).to_string(),
+        Color::Indexed(i) => format!("{};5;{}", base + 8, i),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_encode_diff_only_changed_cells() {
+        let area = Rect::new(0, 0, 10, 2);
+        let previous = Buffer::empty(area);
+        let mut next = Buffer::empty(area);
+        next.set_string(2, 1, "hi", Style::default().fg(Color::Rgb(1, 2, 3)));
+
+        assert_eq!(
+            encode_diff(&previous, &next),
+            "\x1b[2;3H\x1b[0;38;2;1;2;3mhi\x1b[0m"
+        );
+        assert_eq!(encode_diff(&next, &next), "");
+    }
+
+    #[test]
+    fn test_sgr_includes_modifiers_and_background() {
+        let mut cell = Cell::default();
+        cell.set_style(
+            Style::default()
+                .fg(Color::Red)
+                .bg(Color::Indexed(236))
+                .add_modifier(Modifier::BOLD),
+        );
+        assert_eq!(sgr(&cell), "\x1b[0;1;31;48;5;236m");
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::Modifier,
+};
+
+use crate::raster::rgb;
+use crate::record::{FrameSink, FrameThrottle};
+use crate::theme::Theme;
+
+const CELL_WIDTH: u32 = 9;
+const CELL_HEIGHT: u32 = 18;
+const FONT_SIZE: u32 = 15;
+
+/// Writes rendered frames as one SVG that plays them with a CSS keyframe animation.
+/// Frames are stacked vertically and scrolled into view, and rows shared between
+/// frames are defined once, so the output stays small and diffs well.
+pub struct SvgWriter {
+    path: PathBuf,
+    width: u16,
+    height: u16,
+    default_fg: [u8; 3],
+    default_bg: [u8; 3],
+    throttle: FrameThrottle,
+    /// Markup of each distinct row, in order of first appearance
+    rows: Vec<String>,
+    row_ids: HashMap<String, usize>,
+    /// Start time and row ids of each frame
+    frames: Vec<(u64, Vec<usize>)>,
+}
+
+impl SvgWriter {
+    pub fn create(path: &Path, width: u16, height: u16, fps: u32, theme: &Theme) -> Result<Self> {
+        // Fail early rather than after rendering every frame
+        File::create(path)
+            .with_context(|| format!("Failed to create SVG file: {}", path.display()))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            width,
+            height,
+            default_fg: rgb(theme.file_tree_default).unwrap_or([229, 229, 229]),
+            default_bg: rgb(theme.background_right).unwrap_or([0, 0, 0]),
+            throttle: FrameThrottle::new(fps),
+            rows: Vec::new(),
+            row_ids: HashMap::new(),
+            frames: Vec::new(),
+        })
+    }
+
+    fn add_frame(&mut self, start_ms: u64, buffer: &Buffer) {
+        let area = buffer.area;
+        let ids = (0..area.height)
+            .map(|y| {
+                let cells: Vec<&Cell> = (0..area.width)
+                    .map(|x| &buffer[(area.x + x, area.y + y)])
+                    .collect();
+                let markup = self.row_markup(&cells);
+                let next_id = self.rows.len();
+                *self.row_ids.entry(markup.clone()).or_insert_with(|| {
+                    self.rows.push(markup);
+                    next_id
+                })
+            })
+            .collect();
+        self.frames.push((start_ms, ids));
+    }
+
+    /// Background rectangles and styled text spans for one row of cells
+    fn row_markup(&self, cells: &[&Cell]) -> String {
+        let mut backgrounds = String::new();
+        let mut text = String::new();
+
+        let mut col = 0;
+        while col < cells.len() {
+            let cell = cells[col];
+            let run = cells[col..]
+                .iter()
+                .take_while(|c| c.fg == cell.fg && c.bg == cell.bg && c.modifier == cell.modifier)
+                .count();
+
+            let (mut fg, mut bg) = (rgb(cell.fg), rgb(cell.bg));
+            if cell.modifier.contains(Modifier::REVERSED) {
+                (fg, bg) = (bg.or(Some(self.default_bg)), fg.or(Some(self.default_fg)));
+            }
+            if let Some(bg) = bg.filter(|&bg| bg != self.default_bg) {
+                let _ = write!(
+                    backgrounds,
+                    r#"<rect x="{}" width="{}" height="{}" fill="{}"/>"#,
+                    col as u32 * CELL_WIDTH,
+                    run as u32 * CELL_WIDTH,
+                    CELL_HEIGHT,
+                    hex(bg)
+                );
+            }
+
+            let content: String = cells[col..col + run].iter().map(|c| c.symbol()).collect();
+            if !content.trim().is_empty() {
+                let _ = write!(
+                    text,
+                    r#"<tspan x="{}" fill="{}"{}>{}</tspan>"#,
+                    col as u32 * CELL_WIDTH,
+                    hex(fg.unwrap_or(self.default_fg)),
+                    font_attributes(cell.modifier),
+                    escape(&content)
+                );
+            }
+            col += run;
+        }
+
+        format!(
+            r#"{}<text y="{}" xml:space="preserve">{}</text>"#,
+            backgrounds,
+            CELL_HEIGHT - 4,
+            text
+        )
+    }
+
+    fn write_svg(&self, duration_ms: u64) -> Result<()> {
+        let file = File::create(&self.path)
+            .with_context(|| format!("Failed to create SVG file: {}", self.path.display()))?;
+        let mut out = BufWriter::new(file);
+
+        let width = u32::from(self.width) * CELL_WIDTH;
+        let height = u32::from(self.height) * CELL_HEIGHT;
+        let duration_ms = duration_ms.max(1);
+
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="'JetBrains Mono', 'DejaVu Sans Mono', Menlo, Consolas, monospace" font-size="{}px">"#,
+            FONT_SIZE,
+            w = width,
+            h = height
+        )?;
+
+        writeln!(out, "<style>")?;
+        writeln!(
+            out,
+            "#screen {{ animation: play {}ms steps(1, end) infinite; }}",
+            duration_ms
+        )?;
+        writeln!(out, "@keyframes play {{")?;
+        for (i, (start_ms, _)) in self.frames.iter().enumerate() {
+            writeln!(
+                out,
+                "  {:.4}% {{ transform: translateY(-{}px); }}",
+                *start_ms as f64 * 100.0 / duration_ms as f64,
+                i as u32 * height
+            )?;
+        }
+        writeln!(
+            out,
+            "  100% {{ transform: translateY(-{}px); }}",
+            self.frames.len().saturating_sub(1) as u32 * height
+        )?;
+        writeln!(out, "}}")?;
+        writeln!(out, "</style>")?;
+
+        writeln!(out, "<defs>")?;
+        for (id, row) in self.rows.iter().enumerate() {
+            writeln!(out, r#"<g id="r{}">{}</g>"#, id, row)?;
+        }
+        writeln!(out, "</defs>")?;
+
+        writeln!(
+            out,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            hex(self.default_bg)
+        )?;
+        writeln!(out, r#"<g id="screen">"#)?;
+        for (i, (_, rows)) in self.frames.iter().enumerate() {
+            let top = i as u32 * height;
+            writeln!(out, r#"<g transform="translate(0 {})">"#, top)?;
+            for (y, id) in rows.iter().enumerate() {
+                writeln!(
+                    out,
+                    r##"<use xlink:href="#r{}" y="{}"/>"##,
+                    id,
+                    y as u32 * CELL_HEIGHT
+                )?;
+            }
+            writeln!(out, "</g>")?;
+        }
+        writeln!(out, "</g>")?;
+        writeln!(out, "</svg>")?;
+
+        out.flush()?;
+        Ok(())
+    }
+}
+
+impl FrameSink for SvgWriter {
+    fn frame(&mut self, time_ms: u64, buffer: &Buffer) -> Result<()> {
+        if let Some((start_ms, _, frame)) = self.throttle.push(time_ms, buffer) {
+            self.add_frame(start_ms, &frame);
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, time_ms: u64) -> Result<()> {
+        if let Some((start_ms, end_ms, frame)) = self.throttle.flush(time_ms) {
+            self.add_frame(start_ms, &frame);
+            self.write_svg(end_ms)
+        } else {
+            self.write_svg(time_ms)
+        }
+    }
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn font_attributes(modifier: Modifier) -> String {
+    let mut attributes = String::new();
+    if modifier.contains(Modifier::BOLD) {
+        attributes.push_str(r#" font-weight="bold""#);
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        attributes.push_str(r#" font-style="italic""#);
+    }
+    if modifier.contains(Modifier::UNDERLINED) {
+        attributes.push_str(r#" text-decoration="underline""#);
+    }
+    if modifier.contains(Modifier::DIM) {
+        attributes.push_str(r#" opacity="0.6""#);
+    }
+    attributes
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Style},
+    };
+
+    #[test]
+    fn test_row_markup_groups_styled_runs() {
+        let theme = Theme::default();
+        let path = std::env::temp_dir().join(format!("gitlogue_svg_{}.svg", std::process::id()));
+        let writer = SvgWriter::create(&path, 8, 1, 20, &theme).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 1));
+        buffer.set_string(
+            0,
+            0,
+            "a<b",
+            Style::default()
+                .fg(Color::Rgb(255, 0, 0))
+                .bg(Color::Rgb(0, 0, 255))
+                .add_modifier(Modifier::BOLD),
+        );
+        let cells: Vec<&Cell> = (0..8).map(|x| &buffer[(x, 0)]).collect();
+
+        assert_eq!(
+            writer.row_markup(&cells),
+            concat!(
+                r##"<rect x="0" width="27" height="18" fill="#0000ff"/>"##,
+                r##"<text y="14" xml:space="preserve">"##,
+                r##"<tspan x="0" fill="#ff0000" font-weight="bold">a&lt;b</tspan></text>"##
+            )
+        );
+    }
+}
@@ -31,41 +31,161 @@ pub mod zig;
 use std::path::Path;
 use tree_sitter::Language;
 
-pub fn get_language(path: &Path) -> Option<(Language, &'static str)> {
+/// Names of the supported languages, as used in theme files
+pub const LANGUAGE_NAMES: &[&str] = &[
+    "bash",
+    "c",
+    "clojure",
+    "cpp",
+    "csharp",
+    "css",
+    "dart",
+    "elixir",
+    "erlang",
+    "go",
+    "haskell",
+    "html",
+    "java",
+    "javascript",
+    "json",
+    "kotlin",
+    "lua",
+    "markdown",
+    "php",
+    "python",
+    "ruby",
+    "rust",
+    "scala",
+    "svelte",
+    "swift",
+    "typescript",
+    "xml",
+    "yaml",
+    "zig",
+];
+
+/// Language name, grammar and highlight query for a file
+pub fn get_language(path: &Path) -> Option<(&'static str, Language, &'static str)> {
     let extension = path.extension()?.to_str()?;
 
     match extension {
-        "sh" | "bash" | "zsh" => Some((bash::language(), bash::HIGHLIGHT_QUERY)),
+        "sh" | "bash" | "zsh" => Some(("bash", bash::language(), bash::HIGHLIGHT_QUERY)),
         // C++ before C to handle .h files (can be either)
         "cpp" | "cc" | "cxx" | "c++" | "C" | "CPP" | "hpp" | "hh" | "hxx" | "h++" | "H" | "HPP"
-        | "tcc" | "inl" => Some((cpp::language(), cpp::HIGHLIGHT_QUERY)),
-        "c" | "h" => Some((c::language(), c::HIGHLIGHT_QUERY)),
-        "clj" | "cljs" | "cljc" | "edn" => Some((clojure::language(), clojure::HIGHLIGHT_QUERY)),
-        "cs" | "csx" => Some((csharp::language(), csharp::HIGHLIGHT_QUERY)),
-        "css" | "scss" | "sass" => Some((css::language(), css::HIGHLIGHT_QUERY)),
-        "dart" => Some((dart::language(), dart::HIGHLIGHT_QUERY)),
-        "ex" | "exs" => Some((elixir::language(), elixir::HIGHLIGHT_QUERY)),
-        "erl" | "hrl" | "es" | "escript" => Some((erlang::language(), erlang::HIGHLIGHT_QUERY)),
-        "go" => Some((go_lang::language(), go_lang::HIGHLIGHT_QUERY)),
-        "hs" | "lhs" => Some((haskell::language(), haskell::HIGHLIGHT_QUERY)),
-        "html" | "htm" => Some((html::language(), html::HIGHLIGHT_QUERY)),
-        "java" => Some((java::language(), java::HIGHLIGHT_QUERY)),
-        "js" | "jsx" | "mjs" | "cjs" => Some((javascript::language(), javascript::HIGHLIGHT_QUERY)),
-        "json" | "jsonc" => Some((json::language(), json::HIGHLIGHT_QUERY)),
-        "kt" | "kts" => Some((kotlin::language(), kotlin::HIGHLIGHT_QUERY)),
-        "lua" => Some((lua::language(), lua::HIGHLIGHT_QUERY)),
-        "md" | "markdown" => Some((markdown::language(), markdown::HIGHLIGHT_QUERY)),
-        "php" | "php3" | "php4" | "php5" | "phtml" => Some((php::language(), php::HIGHLIGHT_QUERY)),
-        "py" | "pyw" => Some((python::language(), python::HIGHLIGHT_QUERY)),
-        "rb" | "rbw" | "rake" | "gemspec" => Some((ruby::language(), ruby::HIGHLIGHT_QUERY)),
-        "rs" => Some((rust::language(), rust::HIGHLIGHT_QUERY)),
-        "scala" | "sc" | "sbt" => Some((scala::language(), scala::HIGHLIGHT_QUERY)),
-        "svelte" => Some((svelte::language(), svelte::HIGHLIGHT_QUERY)),
-        "swift" => Some((swift::language(), swift::HIGHLIGHT_QUERY)),
-        "ts" | "tsx" | "mts" | "cts" => Some((typescript::language(), typescript::HIGHLIGHT_QUERY)),
-        "xml" | "svg" | "xsl" | "xslt" => Some((xml::language(), xml::HIGHLIGHT_QUERY)),
-        "yaml" | "yml" => Some((yaml::language(), yaml::HIGHLIGHT_QUERY)),
-        "zig" => Some((zig::language(), zig::HIGHLIGHT_QUERY)),
+        | "tcc" | "inl" => Some(("cpp", cpp::language(), cpp::HIGHLIGHT_QUERY)),
+        "c" | "h" => Some(("c", c::language(), c::HIGHLIGHT_QUERY)),
+        "clj" | "cljs" | "cljc" | "edn" => {
+            Some(("clojure", clojure::language(), clojure::HIGHLIGHT_QUERY))
+        }
+        "cs" | "csx" => Some(("csharp", csharp::language(), csharp::HIGHLIGHT_QUERY)),
+        "css" | "scss" | "sass" => Some(("css", css::language(), css::HIGHLIGHT_QUERY)),
+        "dart" => Some(("dart", dart::language(), dart::HIGHLIGHT_QUERY)),
+        "ex" | "exs" => Some(("elixir", elixir::language(), elixir::HIGHLIGHT_QUERY)),
+        "erl" | "hrl" | "es" | "escript" => {
+            Some(("erlang", erlang::language(), erlang::HIGHLIGHT_QUERY))
+        }
+        "go" => Some(("go", go_lang::language(), go_lang::HIGHLIGHT_QUERY)),
+        "hs" | "lhs" => Some(("haskell", haskell::language(), haskell::HIGHLIGHT_QUERY)),
+        "html" | "htm" => Some(("html", html::language(), html::HIGHLIGHT_QUERY)),
+        "java" => Some(("java", java::language(), java::HIGHLIGHT_QUERY)),
+        "js" | "jsx" | "mjs" | "cjs" => Some((
+            "javascript",
+            javascript::language(),
+            javascript::HIGHLIGHT_QUERY,
+        )),
+        "json" | "jsonc" => Some(("json", json::language(), json::HIGHLIGHT_QUERY)),
+        "kt" | "kts" => Some(("kotlin", kotlin::language(), kotlin::HIGHLIGHT_QUERY)),
+        "lua" => Some(("lua", lua::language(), lua::HIGHLIGHT_QUERY)),
+        "md" | "markdown" => Some(("markdown", markdown::language(), markdown::HIGHLIGHT_QUERY)),
+        "php" | "php3" | "php4" | "php5" | "phtml" => {
+            Some(("php", php::language(), php::HIGHLIGHT_QUERY))
+        }
+        "py" | "pyw" => Some(("python", python::language(), python::HIGHLIGHT_QUERY)),
+        "rb" | "rbw" | "rake" | "gemspec" => {
+            Some(("ruby", ruby::language(), ruby::HIGHLIGHT_QUERY))
+        }
+        "rs" => Some(("rust", rust::language(), rust::HIGHLIGHT_QUERY)),
+        "scala" | "sc" | "sbt" => Some(("scala", scala::language(), scala::HIGHLIGHT_QUERY)),
+        "svelte" => Some(("svelte", svelte::language(), svelte::HIGHLIGHT_QUERY)),
+        "swift" => Some(("swift", swift::language(), swift::HIGHLIGHT_QUERY)),
+        "ts" | "tsx" | "mts" | "cts" => Some((
+            "typescript",
+            typescript::language(),
+            typescript::HIGHLIGHT_QUERY,
+        )),
+        "xml" | "svg" | "xsl" | "xslt" => Some(("xml", xml::language(), xml::HIGHLIGHT_QUERY)),
+        "yaml" | "yml" => Some(("yaml", yaml::language(), yaml::HIGHLIGHT_QUERY)),
+        "zig" => Some(("zig", zig::language(), zig::HIGHLIGHT_QUERY)),
         _ => None,
     }
 }
+
+/// Editing conventions used to simulate an editor's auto-pairing and auto-indentation
+#[derive(Debug, Clone, Copy)]
+pub struct EditorRules {
+    /// Indentation unit used when the file itself gives no hint
+    pub indent_unit: &'static str,
+    /// Trailing characters that open an indented block on the next line
+    pub indent_after: &'static [char],
+    /// Bracket and quote pairs that are closed automatically
+    pub pairs: &'static [(char, char)],
+}
+
+const BRACKETS: &[char] = &['{', '(', '['];
+const BRACKETS_AND_COLON: &[char] = &['{', '(', '[', ':'];
+
+const C_LIKE_PAIRS: &[(char, char)] =
+    &[('(', ')'), ('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')];
+const NO_SINGLE_QUOTE_PAIRS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}'), ('"', '"')];
+const SCRIPT_PAIRS: &[(char, char)] = &[
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('"', '"'),
+    ('\'', '\''),
+    ('`', '`'),
+];
+const MARKUP_PAIRS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('"', '"')];
+
+/// Editing conventions for a language from `LANGUAGE_NAMES`, or defaults for unknown files
+pub fn get_editor_rules(language: Option<&str>) -> EditorRules {
+    match language.unwrap_or_default() {
+        "python" => EditorRules {
+            indent_unit: "    ",
+            indent_after: BRACKETS_AND_COLON,
+            pairs: C_LIKE_PAIRS,
+        },
+        "yaml" => EditorRules {
+            indent_unit: "  ",
+            indent_after: BRACKETS_AND_COLON,
+            pairs: C_LIKE_PAIRS,
+        },
+        "go" => EditorRules {
+            indent_unit: "\t",
+            indent_after: BRACKETS,
+            pairs: SCRIPT_PAIRS,
+        },
+        // Single quotes mark lifetimes, char literals or quoting in these languages
+        "rust" | "clojure" | "haskell" | "erlang" => EditorRules {
+            indent_unit: "    ",
+            indent_after: BRACKETS,
+            pairs: NO_SINGLE_QUOTE_PAIRS,
+        },
+        "javascript" | "typescript" | "svelte" | "ruby" | "lua" | "elixir" | "dart" | "json"
+        | "bash" => EditorRules {
+            indent_unit: "  ",
+            indent_after: BRACKETS,
+            pairs: SCRIPT_PAIRS,
+        },
+        "markdown" | "html" | "xml" => EditorRules {
+            indent_unit: "  ",
+            indent_after: &[],
+            pairs: MARKUP_PAIRS,
+        },
+        _ => EditorRules {
+            indent_unit: "    ",
+            indent_after: BRACKETS,
+            pairs: C_LIKE_PAIRS,
+        },
+    }
+}
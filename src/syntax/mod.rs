@@ -1,12 +1,12 @@
 pub mod languages;
 
 use crate::theme::Theme;
-use ratatui::style::Color;
+use ratatui::style::Style;
 use std::path::Path;
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Language, Parser, Query, QueryCursor};
 
-pub use languages::get_language;
+pub use languages::{get_editor_rules, get_language, EditorRules, LANGUAGE_NAMES};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenType {
@@ -23,26 +23,159 @@ pub enum TokenType {
     String,
     Type,
     Variable,
+    // Finer categories, colored like the broader ones unless the theme sets them
+    Attribute,
+    Boolean,
+    BuiltinConstant,
+    BuiltinFunction,
+    BuiltinType,
+    BuiltinVariable,
+    CommentDoc,
+    Constructor,
+    Macro,
+    MarkupHeading,
+    MarkupLink,
+    MarkupRaw,
+    Method,
+    Module,
+    StringEscape,
+    StringSpecial,
+    Tag,
 }
 
 impl TokenType {
-    pub fn color(&self, theme: &Theme) -> Color {
+    /// Token type for a tree-sitter capture name, trying the most specific name first,
+    /// so `function.method.call` is a method and `keyword.return` a keyword
+    pub fn from_capture(name: &str) -> Option<Self> {
+        let mut name = name;
+        loop {
+            if let Some(token_type) = Self::from_capture_exact(name) {
+                return Some(token_type);
+            }
+            name = name.rsplit_once('.')?.0;
+        }
+    }
+
+    fn from_capture_exact(name: &str) -> Option<Self> {
+        Some(match name {
+            "annotation" | "attribute" | "decorator" => TokenType::Attribute,
+            "boolean" => TokenType::Boolean,
+            "character" => TokenType::String,
+            "character.special" | "escape" | "string.escape" => TokenType::StringEscape,
+            "class" | "enum" | "interface" | "struct" | "trait" => TokenType::Type,
+            "comment" => TokenType::Comment,
+            "comment.doc" | "comment.documentation" => TokenType::CommentDoc,
+            "conditional" | "exception" | "include" | "repeat" | "storageclass" => {
+                TokenType::Keyword
+            }
+            "constant" => TokenType::Constant,
+            "constant.builtin" | "constant.null" => TokenType::BuiltinConstant,
+            "constructor" => TokenType::Constructor,
+            "delimiter" => TokenType::Punctuation,
+            "field" => TokenType::Property,
+            "float" => TokenType::Number,
+            "function" => TokenType::Function,
+            "function.builtin" | "function.method.builtin" => TokenType::BuiltinFunction,
+            "function.macro" | "macro" => TokenType::Macro,
+            "function.method" | "method" => TokenType::Method,
+            "identifier" => TokenType::Variable,
+            "keyword" => TokenType::Keyword,
+            "label" => TokenType::Label,
+            "markup.heading" | "text.title" => TokenType::MarkupHeading,
+            "markup.link" | "string.special.url" | "text.reference" | "text.uri" => {
+                TokenType::MarkupLink
+            }
+            "markup.raw" | "text.literal" => TokenType::MarkupRaw,
+            "module" | "namespace" => TokenType::Module,
+            "number" => TokenType::Number,
+            "operator" => TokenType::Operator,
+            "parameter" => TokenType::Parameter,
+            "property" => TokenType::Property,
+            "punctuation" => TokenType::Punctuation,
+            "regexp" | "string.regex" | "string.regexp" | "string.special" | "symbol" => {
+                TokenType::StringSpecial
+            }
+            "special" => TokenType::Operator,
+            "string" => TokenType::String,
+            "tag" => TokenType::Tag,
+            "text" => TokenType::String,
+            "type" => TokenType::Type,
+            "type.builtin" => TokenType::BuiltinType,
+            "variable" => TokenType::Variable,
+            "variable.builtin" => TokenType::BuiltinVariable,
+            // Internal/special markers such as `_name`, `none` and `spell` are skipped
+            _ => return None,
+        })
+    }
+
+    /// Theme field the token type is colored with
+    fn field(&self) -> &'static str {
         match self {
-            TokenType::Comment => theme.syntax_comment,
-            TokenType::Constant => theme.syntax_constant,
-            TokenType::Function => theme.syntax_function,
-            TokenType::Keyword => theme.syntax_keyword,
-            TokenType::Label => theme.syntax_label,
-            TokenType::Number => theme.syntax_number,
-            TokenType::Operator => theme.syntax_operator,
-            TokenType::Parameter => theme.syntax_parameter,
-            TokenType::Property => theme.syntax_property,
-            TokenType::Punctuation => theme.syntax_punctuation,
-            TokenType::String => theme.syntax_string,
-            TokenType::Type => theme.syntax_type,
-            TokenType::Variable => theme.syntax_variable,
+            TokenType::Comment => "syntax_comment",
+            TokenType::Constant => "syntax_constant",
+            TokenType::Function => "syntax_function",
+            TokenType::Keyword => "syntax_keyword",
+            TokenType::Label => "syntax_label",
+            TokenType::Number => "syntax_number",
+            TokenType::Operator => "syntax_operator",
+            TokenType::Parameter => "syntax_parameter",
+            TokenType::Property => "syntax_property",
+            TokenType::Punctuation => "syntax_punctuation",
+            TokenType::String => "syntax_string",
+            TokenType::Type => "syntax_type",
+            TokenType::Variable => "syntax_variable",
+            TokenType::Attribute => "syntax_attribute",
+            TokenType::Boolean => "syntax_boolean",
+            TokenType::BuiltinConstant
+            | TokenType::BuiltinFunction
+            | TokenType::BuiltinType
+            | TokenType::BuiltinVariable => "syntax_builtin",
+            TokenType::CommentDoc => "syntax_comment_doc",
+            TokenType::Constructor => "syntax_constructor",
+            TokenType::Macro => "syntax_macro",
+            TokenType::MarkupHeading => "syntax_markup_heading",
+            TokenType::MarkupLink => "syntax_markup_link",
+            TokenType::MarkupRaw => "syntax_markup_raw",
+            TokenType::Method => "syntax_method",
+            TokenType::Module => "syntax_module",
+            TokenType::StringEscape => "syntax_string_escape",
+            TokenType::StringSpecial => "syntax_string_special",
+            TokenType::Tag => "syntax_tag",
         }
     }
+
+    /// Broader category used when the theme leaves a finer one unset
+    fn fallback(&self) -> Option<TokenType> {
+        match self {
+            TokenType::Attribute => Some(TokenType::Keyword),
+            TokenType::Boolean | TokenType::BuiltinConstant => Some(TokenType::Constant),
+            TokenType::BuiltinFunction | TokenType::Macro | TokenType::Method => {
+                Some(TokenType::Function)
+            }
+            TokenType::BuiltinType
+            | TokenType::Constructor
+            | TokenType::Module
+            | TokenType::Tag => Some(TokenType::Type),
+            TokenType::BuiltinVariable => Some(TokenType::Variable),
+            TokenType::CommentDoc => Some(TokenType::Comment),
+            TokenType::MarkupHeading
+            | TokenType::MarkupLink
+            | TokenType::MarkupRaw
+            | TokenType::StringEscape
+            | TokenType::StringSpecial => Some(TokenType::String),
+            _ => None,
+        }
+    }
+
+    /// Style from the theme, with the finer category's color and font styles on top of
+    /// the broader one's, and the language's on top of the theme's
+    pub fn style(&self, theme: &Theme, language: Option<&str>) -> Style {
+        let base = self
+            .fallback()
+            .map(|fallback| fallback.style(theme, language))
+            .unwrap_or_default();
+        base.patch(theme.syntax_style(self.field(), language))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +187,7 @@ pub struct HighlightSpan {
 
 pub struct Highlighter {
     parser: Parser,
+    language_name: Option<&'static str>,
     language: Option<Language>,
     query: Option<Query>,
     query_source: Option<String>,
@@ -74,6 +208,7 @@ impl Clone for Highlighter {
 
         Self {
             parser: new_parser,
+            language_name: self.language_name,
             language: self.language.clone(),
             query,
             query_source: self.query_source.clone(),
@@ -87,6 +222,7 @@ impl Highlighter {
     pub fn new() -> Self {
         Self {
             parser: Parser::new(),
+            language_name: None,
             language: None,
             query: None,
             query_source: None,
@@ -96,9 +232,10 @@ impl Highlighter {
     }
 
     pub fn set_language_from_path(&mut self, path: &str) -> bool {
-        if let Some((language, query_source)) = get_language(Path::new(path)) {
+        if let Some((name, language, query_source)) = get_language(Path::new(path)) {
             if self.parser.set_language(&language).is_ok() {
                 if let Ok(query) = Query::new(&language, query_source) {
+                    self.language_name = Some(name);
                     self.language = Some(language);
                     self.query = Some(query);
                     self.query_source = Some(query_source.to_string());
@@ -109,6 +246,7 @@ impl Highlighter {
             }
         }
         // Language not supported - clear previous language settings
+        self.language_name = None;
         self.language = None;
         self.query = None;
         self.query_source = None;
@@ -117,6 +255,11 @@ impl Highlighter {
         false
     }
 
+    /// Name of the current language, as used in theme files
+    pub fn language_name(&self) -> Option<&'static str> {
+        self.language_name
+    }
+
     pub fn highlight(&mut self, source: &str) -> Vec<HighlightSpan> {
         let mut spans = Vec::new();
 
@@ -145,50 +288,9 @@ impl Highlighter {
         while let Some(query_match) = matches.next() {
             for capture in query_match.captures {
                 let node = capture.node;
-                let capture_name = &query.capture_names()[capture.index as usize];
-
-                // Handle dotted capture names like "keyword.function" -> "keyword"
-                let base_name = capture_name.split('.').next().unwrap_or(capture_name);
-
-                let token_type = match base_name {
-                    "annotation" | "attribute" | "decorator" => TokenType::Keyword,
-                    "boolean" => TokenType::Constant,
-                    "character" => TokenType::String,
-                    "class" | "constructor" | "enum" | "interface" | "struct" | "trait" => {
-                        TokenType::Type
-                    }
-                    "comment" => TokenType::Comment,
-                    "conditional" | "exception" | "include" | "repeat" | "storageclass" => {
-                        TokenType::Keyword
-                    }
-                    "constant" => TokenType::Constant,
-                    "delimiter" => TokenType::Punctuation,
-                    "escape" => TokenType::Operator,
-                    "field" => TokenType::Property,
-                    "float" => TokenType::Number,
-                    "function" => TokenType::Function,
-                    "identifier" => TokenType::Variable,
-                    "keyword" => TokenType::Keyword,
-                    "label" => TokenType::Label,
-                    "macro" | "method" => TokenType::Function,
-                    "module" | "namespace" => TokenType::Type,
-                    "number" => TokenType::Number,
-                    "operator" => TokenType::Operator,
-                    "parameter" => TokenType::Parameter,
-                    "property" => TokenType::Property,
-                    "punctuation" => TokenType::Punctuation,
-                    "regexp" => TokenType::String,
-                    "special" => TokenType::Operator,
-                    "string" => TokenType::String,
-                    "tag" => TokenType::Type,
-                    "text" => TokenType::String,
-                    "type" => TokenType::Type,
-                    "variable" => TokenType::Variable,
-                    // Skip internal/special markers
-                    "__name__" | "_name" | "_op" | "_type" | "embedded" | "none" | "spell" => {
-                        continue
-                    }
-                    _ => continue,
+                let capture_name = query.capture_names()[capture.index as usize];
+                let Some(token_type) = TokenType::from_capture(capture_name) else {
+                    continue;
                 };
 
                 spans.push(HighlightSpan {
@@ -209,3 +311,46 @@ impl Default for Highlighter {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures_map_to_most_specific_token_type() {
+        assert_eq!(
+            TokenType::from_capture("function.method.call"),
+            Some(TokenType::Method)
+        );
+        assert_eq!(
+            TokenType::from_capture("keyword.conditional.ternary"),
+            Some(TokenType::Keyword)
+        );
+        assert_eq!(
+            TokenType::from_capture("string.special.url"),
+            Some(TokenType::MarkupLink)
+        );
+        assert_eq!(
+            TokenType::from_capture("text.title"),
+            Some(TokenType::MarkupHeading)
+        );
+        assert_eq!(TokenType::from_capture("_name"), None);
+        assert_eq!(TokenType::from_capture("spell"), None);
+    }
+
+    #[test]
+    fn test_finer_token_types_fall_back_to_broader_colors() {
+        let theme = Theme::default();
+        assert_eq!(
+            TokenType::Macro.style(&theme, None),
+            Style::new().fg(theme.syntax_function)
+        );
+        assert_eq!(
+            TokenType::StringEscape.style(&theme, Some("rust")),
+            Style::new().fg(theme.syntax_string)
+        );
+        for field in Theme::OPTIONAL_FIELDS {
+            assert_eq!(theme.syntax_style(field, None), Style::new(), "{}", field);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::env;
+
+use clap::ValueEnum;
+use ratatui::style::Color;
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// Detect from `COLORTERM`, `TERM` and `NO_COLOR`
+    #[default]
+    Auto,
+    /// 24-bit RGB colors
+    Truecolor,
+    /// The xterm 256-color palette
+    #[value(name = "256")]
+    Ansi256,
+    /// The 16 ANSI colors
+    #[value(name = "16")]
+    Ansi16,
+    /// No colors at all
+    None,
+}
+
+impl ColorMode {
+    /// Resolve `Auto` from the environment
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => Self::detect(
+                env::var("COLORTERM").ok().as_deref(),
+                env::var("TERM").ok().as_deref(),
+                env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()),
+            ),
+            mode => mode,
+        }
+    }
+
+    fn detect(colorterm: Option<&str>, term: Option<&str>, no_color: bool) -> Self {
+        if no_color {
+            return Self::None;
+        }
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return Self::Truecolor;
+        }
+        match term {
+            // Windows terminals don't set TERM but handle RGB colors
+            None if cfg!(windows) => Self::Truecolor,
+            None | Some("dumb") => Self::None,
+            Some(term) if term.contains("direct") || term.contains("truecolor") => Self::Truecolor,
+            Some(term) if term.contains("256color") => Self::Ansi256,
+            Some(_) => Self::Ansi16,
+        }
+    }
+
+    /// Map a color onto the palette of this mode
+    pub fn convert(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::None, _) => Color::Reset,
+            (Self::Ansi256, Color::Rgb(r, g, b)) => nearest_256([r, g, b]),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_16([r, g, b]),
+            (Self::Ansi16, Color::Indexed(index)) => match indexed_rgb(index) {
+                Some(rgb) => nearest_16(rgb),
+                None => color,
+            },
+            _ => color,
+        }
+    }
+}
+
+/// Blend a foreground color into the background, or `None` if the colors can't be mixed
+/// (named ANSI colors and the terminal default have no known RGB value)
+pub fn blend(foreground: Color, background: Color, opacity: f32) -> Option<Color> {
+    let mix = |f: [u8; 3], b: [u8; 3]| {
+        // result = fg * opacity + bg * (1 - opacity)
+        [0, 1, 2].map(|i| (f[i] as f32 * opacity + b[i] as f32 * (1.0 - opacity)) as u8)
+    };
+    match (foreground, background) {
+        (Color::Rgb(fr, fg, fb), Color::Rgb(br, bg, bb)) => {
+            let [r, g, b] = mix([fr, fg, fb], [br, bg, bb]);
+            Some(Color::Rgb(r, g, b))
+        }
+        // Stay within the 256-color palette the theme was converted to
+        (Color::Indexed(f), Color::Indexed(b)) => {
+            Some(nearest_256(mix(indexed_rgb(f)?, indexed_rgb(b)?)))
+        }
+        _ => None,
+    }
+}
+
+/// Levels of each channel in the 6x6x6 color cube (indices 16-231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// RGB value of a 256-color palette entry; the first 16 depend on the terminal
+pub(super) fn indexed_rgb(index: u8) -> Option<[u8; 3]> {
+    match index {
+        0..=15 => None,
+        16..=231 => {
+            let i = index - 16;
+            Some([i / 36, i / 6 % 6, i % 6].map(|level| CUBE_LEVELS[level as usize]))
+        }
+        232..=255 => {
+            let level = 8 + (index - 232) * 10;
+            Some([level; 3])
+        }
+    }
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    (0..3)
+        .map(|i| (i32::from(a[i]) - i32::from(b[i])).pow(2) as u32)
+        .sum()
+}
+
+/// Closest color cube or grayscale ramp entry, skipping the terminal-defined first 16
+fn nearest_256(rgb: [u8; 3]) -> Color {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(value)).abs())
+            .unwrap_or_default() as u8
+    };
+    let [r, g, b] = rgb.map(level);
+    let cube = 16 + 36 * r + 6 * g + b;
+
+    let average = rgb.iter().map(|&c| u32::from(c)).sum::<u32>() / 3;
+    let gray = 232 + ((average.saturating_sub(3) / 10).min(23) as u8);
+
+    [cube, gray]
+        .into_iter()
+        .min_by_key(|&index| indexed_rgb(index).map_or(u32::MAX, |c| distance(c, rgb)))
+        .map_or(Color::Reset, Color::Indexed)
+}
+
+/// Closest ANSI color by hue, so that syntax colors stay distinguishable
+/// instead of collapsing into gray
+fn nearest_16([r, g, b]: [u8; 3]) -> Color {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = f32::from(max - min);
+    let lightness = (u16::from(max) + u16::from(min)) / 2;
+
+    if max < 80 || chroma / f32::from(max) < 0.25 {
+        return match lightness {
+            0..64 => Color::Black,
+            64..144 => Color::DarkGray,
+            144..208 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+
+    let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+    let max = f32::from(max);
+    let hue = if max == r {
+        60.0 * ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    };
+
+    let bright = max >= 230.0 || lightness >= 128;
+    match ((hue + 30.0) / 60.0) as u32 % 6 {
+        0 if bright => Color::LightRed,
+        0 => Color::Red,
+        1 if bright => Color::LightYellow,
+        1 => Color::Yellow,
+        2 if bright => Color::LightGreen,
+        2 => Color::Green,
+        3 if bright => Color::LightCyan,
+        3 => Color::Cyan,
+        4 if bright => Color::LightBlue,
+        4 => Color::Blue,
+        _ if bright => Color::LightMagenta,
+        _ => Color::Magenta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_color_mode() {
+        assert_eq!(
+            ColorMode::detect(Some("truecolor"), Some("xterm-256color"), false),
+            ColorMode::Truecolor
+        );
+        assert_eq!(
+            ColorMode::detect(None, Some("tmux-256color"), false),
+            ColorMode::Ansi256
+        );
+        assert_eq!(
+            ColorMode::detect(None, Some("linux"), false),
+            ColorMode::Ansi16
+        );
+        assert_eq!(
+            ColorMode::detect(Some("truecolor"), Some("xterm"), true),
+            ColorMode::None
+        );
+    }
+
+    #[test]
+    fn test_convert_to_nearest_palette_entry() {
+        let mode = ColorMode::Ansi256;
+        assert_eq!(mode.convert(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(mode.convert(Color::Rgb(26, 27, 38)), Color::Indexed(234));
+        assert_eq!(mode.convert(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+
+        let mode = ColorMode::Ansi16;
+        assert_eq!(mode.convert(Color::Rgb(26, 27, 38)), Color::Black);
+        assert_eq!(mode.convert(Color::Rgb(187, 154, 247)), Color::LightBlue);
+        assert_eq!(mode.convert(Color::Rgb(247, 118, 142)), Color::LightRed);
+        assert_eq!(mode.convert(Color::Rgb(40, 120, 40)), Color::Green);
+        assert_eq!(mode.convert(Color::Indexed(196)), Color::LightRed);
+
+        assert_eq!(ColorMode::None.convert(Color::Red), Color::Reset);
+        assert_eq!(
+            ColorMode::Truecolor.convert(Color::Rgb(1, 2, 3)),
+            Color::Rgb(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn test_blend_stays_in_palette() {
+        assert_eq!(
+            blend(Color::Rgb(200, 100, 0), Color::Rgb(0, 0, 0), 0.5),
+            Some(Color::Rgb(100, 50, 0))
+        );
+        assert_eq!(
+            blend(Color::Indexed(231), Color::Indexed(16), 0.5),
+            Some(Color::Indexed(244))
+        );
+        assert_eq!(blend(Color::Red, Color::Black, 0.5), None);
+        assert_eq!(blend(Color::Rgb(1, 2, 3), Color::Reset, 0.5), None);
+    }
+}
@@ -0,0 +1,233 @@
+use ratatui::style::Color;
+
+use super::color::indexed_rgb;
+use super::Theme;
+
+/// WCAG AA contrast for normal text
+pub const AA_CONTRAST: f64 = 4.5;
+
+/// Below this contrast text is hard to make out at any size
+const READABLE_CONTRAST: f64 = 2.5;
+
+/// Comments, line numbers and other colors meant to recede may have less contrast
+const READABLE_DIM_CONTRAST: f64 = 1.5;
+
+const DIM_FIELDS: &[&str] = &[
+    "editor_line_number",
+    "status_no_commit",
+    "syntax_comment",
+    "syntax_comment_doc",
+    "terminal_output",
+];
+
+/// Text colors by the background they are drawn on
+const PAIRS: &[(&str, &[&str])] = &[
+    (
+        "background_right",
+        &["editor_line_number", "terminal_command", "terminal_output"],
+    ),
+    ("editor_cursor_line_bg", &["editor_line_number_cursor"]),
+    ("editor_cursor_char_bg", &["editor_cursor_char_fg"]),
+    ("terminal_cursor_bg", &["terminal_cursor_fg"]),
+    (
+        "background_left",
+        &[
+            "file_tree_added",
+            "file_tree_deleted",
+            "file_tree_modified",
+            "file_tree_renamed",
+            "file_tree_directory",
+            "file_tree_default",
+            "file_tree_stats_added",
+            "file_tree_stats_deleted",
+            "status_hash",
+            "status_author",
+            "status_date",
+            "status_message",
+            "status_no_commit",
+        ],
+    ),
+    ("file_tree_current_file_bg", &["file_tree_current_file_fg"]),
+    (
+        "editor_line_added_bg",
+        &["editor_line_number", "editor_gutter_added"],
+    ),
+    (
+        "editor_line_modified_bg",
+        &["editor_line_number", "editor_gutter_modified"],
+    ),
+];
+
+/// Backgrounds the editor draws syntax colors on
+const SYNTAX_BACKGROUNDS: &[&str] = &[
+    "background_right",
+    "editor_line_added_bg",
+    "editor_line_modified_bg",
+];
+
+/// Contrast of a text color on the background it is drawn on
+#[derive(Debug, Clone)]
+pub struct Contrast {
+    /// Field name of the text color, prefixed with the language for per-language colors
+    pub foreground: String,
+    pub background: &'static str,
+    pub ratio: f64,
+    /// Lowest readable ratio, which is lower for colors meant to be dim
+    pub minimum: f64,
+}
+
+impl Contrast {
+    pub fn is_readable(&self) -> bool {
+        self.ratio >= self.minimum
+    }
+}
+
+impl Theme {
+    /// Contrast of every text color on its background, skipping colors set by the terminal
+    pub fn contrast(&self) -> Vec<Contrast> {
+        let mut pairs: Vec<(String, Color, &'static str)> = Vec::new();
+        let syntax_fields = Self::FIELDS
+            .iter()
+            .chain(Self::OPTIONAL_FIELDS)
+            .filter(|field| field.starts_with("syntax_"));
+        for field in syntax_fields {
+            if let Some(color) = self.color(field) {
+                for background in SYNTAX_BACKGROUNDS {
+                    pairs.push((field.to_string(), color, background));
+                }
+            }
+        }
+        for (language, styles) in &self.syntax_overrides.languages {
+            for (field, style) in styles {
+                if let Some(color) = style.fg {
+                    let foreground = format!("{}.{}", language, field);
+                    for background in SYNTAX_BACKGROUNDS {
+                        pairs.push((foreground.clone(), color, background));
+                    }
+                }
+            }
+        }
+        for (background, fields) in PAIRS {
+            for field in *fields {
+                if let Some(color) = self.color(field) {
+                    pairs.push((field.to_string(), color, background));
+                }
+            }
+        }
+
+        pairs
+            .into_iter()
+            .filter_map(|(foreground, color, background)| {
+                let ratio = contrast_ratio(color, self.color(background)?)?;
+                let field = foreground.rsplit('.').next().unwrap_or(&foreground);
+                let minimum = if DIM_FIELDS.contains(&field) {
+                    READABLE_DIM_CONTRAST
+                } else {
+                    READABLE_CONTRAST
+                };
+                Some(Contrast {
+                    foreground,
+                    background,
+                    ratio,
+                    minimum,
+                })
+            })
+            .collect()
+    }
+}
+
+/// WCAG relative luminance of a color, or `None` for colors set by the terminal
+fn luminance(color: Color) -> Option<f64> {
+    let [r, g, b] = match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(index) => indexed_rgb(index)?,
+        _ => return None,
+    };
+    let linear = |channel: u8| {
+        let c = f64::from(channel) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    Some(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+}
+
+/// WCAG contrast ratio between two colors, from 1 (none) to 21 (black on white)
+pub fn contrast_ratio(foreground: Color, background: Color) -> Option<f64> {
+    let (a, b) = (luminance(foreground)?, luminance(background)?);
+    Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let ratio = |fg, bg| (contrast_ratio(fg, bg).unwrap() * 100.0).round() / 100.0;
+        assert_eq!(ratio(Color::Rgb(0, 0, 0), Color::Rgb(255, 255, 255)), 21.0);
+        assert_eq!(ratio(Color::Rgb(255, 255, 255), Color::Rgb(0, 0, 0)), 21.0);
+        assert_eq!(
+            ratio(Color::Rgb(118, 118, 118), Color::Rgb(255, 255, 255)),
+            4.54
+        );
+        assert_eq!(ratio(Color::Indexed(16), Color::Indexed(231)), 21.0);
+        assert_eq!(contrast_ratio(Color::Red, Color::Rgb(0, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_builtin_themes_are_readable() {
+        for name in Theme::available_themes() {
+            let theme = Theme::builtin(name).unwrap();
+            let contrast = theme.contrast();
+            assert!(contrast.len() > 30, "{}", name);
+            let unreadable: Vec<String> = contrast
+                .iter()
+                .filter(|pair| !pair.is_readable())
+                .map(|pair| format!("{} ({:.2})", pair.foreground, pair.ratio))
+                .collect();
+            assert!(unreadable.is_empty(), "{}: {:?}", name, unreadable);
+        }
+    }
+
+    #[test]
+    fn test_contrast_covers_language_colors() {
+        let mut theme = Theme::default();
+        let black = Style::new().fg(Color::Rgb(0, 0, 0));
+        theme.set_language_style("rust", "syntax_macro", black);
+        let find = |contrast: &[Contrast], foreground: &str, background: &str| {
+            contrast
+                .iter()
+                .find(|pair| pair.foreground == foreground && pair.background == background)
+                .map(Contrast::is_readable)
+        };
+
+        let contrast = theme.contrast();
+        let find_right = |contrast: &[Contrast], foreground: &str| {
+            find(contrast, foreground, "background_right")
+        };
+        assert_eq!(find_right(&contrast, "rust.syntax_macro"), Some(false));
+        assert_eq!(find_right(&contrast, "syntax_macro"), None);
+        assert_eq!(find_right(&contrast, "syntax_keyword"), Some(true));
+        // Syntax colors are also drawn on the change marker line backgrounds
+        assert_eq!(
+            find(&contrast, "syntax_keyword", "editor_line_added_bg"),
+            Some(true)
+        );
+        assert_eq!(
+            find(&contrast, "rust.syntax_macro", "editor_line_modified_bg"),
+            Some(false)
+        );
+
+        // Text on a transparent background is up to the terminal
+        let contrast = theme.with_transparent_background().contrast();
+        assert_eq!(find_right(&contrast, "rust.syntax_macro"), None);
+        assert_eq!(
+            find(&contrast, "editor_cursor_char_fg", "editor_cursor_char_bg"),
+            Some(true)
+        );
+    }
+}
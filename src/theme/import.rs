@@ -0,0 +1,754 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use ratatui::style::Color;
+use serde_json::Value;
+
+use super::{SyntaxOverrides, Theme};
+
+type Rgb = [u8; 3];
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// A theme converted from another application's color scheme
+pub struct ImportedTheme {
+    /// Theme name suggested by the file name
+    pub name: String,
+    /// Format the theme was converted from
+    pub format: &'static str,
+    pub theme: Theme,
+    source: String,
+}
+
+impl ImportedTheme {
+    /// Contents of a user theme file defining every color
+    pub fn to_toml(&self) -> String {
+        let mut out = format!("# Imported from {} ({})\n\n", self.source, self.format);
+        for field in Theme::FIELDS.iter().chain(Theme::OPTIONAL_FIELDS) {
+            if let Some(Color::Rgb(r, g, b)) = self.theme.color(field) {
+                let _ = writeln!(out, "{} = \"{}\"", field, hex([r, g, b]));
+            }
+        }
+        out
+    }
+}
+
+/// Convert a VS Code color theme, base16 scheme, or Alacritty/kitty color config
+pub fn import_theme(path: &Path) -> Result<ImportedTheme> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let (format, theme) = if extension == "json" || text.trim_start().starts_with('{') {
+        ("VS Code", vscode(&text)?)
+    } else if extension == "toml" {
+        let value: toml::Value = toml::from_str(&text).context("Failed to parse TOML")?;
+        let mut keys = BTreeMap::new();
+        flatten_toml(&value, "", &mut keys);
+        ("Alacritty", alacritty(&keys)?)
+    } else if extension == "yaml" || extension == "yml" {
+        let keys = flatten_yaml(&text);
+        if keys.keys().any(|key| key.ends_with("base00")) {
+            ("base16", base16(&keys)?)
+        } else {
+            ("Alacritty", alacritty(&keys)?)
+        }
+    } else {
+        ("kitty", kitty(&text)?)
+    };
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    Ok(ImportedTheme {
+        name: theme_name(&path.file_stem().unwrap_or_default().to_string_lossy()),
+        format,
+        theme,
+        source: file_name.into_owned(),
+    })
+}
+
+/// Terminal colors every format is reduced to before mapping them onto a theme
+struct Palette {
+    background: Rgb,
+    foreground: Rgb,
+    /// The normal then bright variants of `ANSI_NAMES`
+    ansi: [Option<Rgb>; 16],
+    selection: Option<Rgb>,
+    cursor: Option<Rgb>,
+}
+
+impl Palette {
+    fn new(background: Option<Rgb>, foreground: Option<Rgb>) -> Result<Self> {
+        Ok(Self {
+            background: background.context("No background color found")?,
+            foreground: foreground.context("No foreground color found")?,
+            ansi: [None; 16],
+            selection: None,
+            cursor: None,
+        })
+    }
+
+    fn theme(&self) -> Theme {
+        let (bg, fg) = (self.background, self.foreground);
+        let normal = |i: usize| self.ansi[i].unwrap_or(fg);
+        let bright = |i: usize| self.ansi[i + 8].unwrap_or_else(|| normal(i));
+        let muted = self.ansi[8].unwrap_or_else(|| mix(fg, bg, 0.5));
+        let (red, green, yellow, blue) = (normal(1), normal(2), normal(3), normal(4));
+        let (magenta, cyan) = (normal(5), normal(6));
+        let cursor = self.cursor.unwrap_or(fg);
+        let selection = self.selection.unwrap_or_else(|| mix(bg, fg, 0.15));
+        let c = |rgb: Rgb| Color::Rgb(rgb[0], rgb[1], rgb[2]);
+
+        Theme {
+            background_left: c(mix(bg, fg, 0.05)),
+            background_right: c(bg),
+
+            editor_line_number: c(muted),
+            editor_line_number_cursor: c(fg),
+            editor_separator: c(muted),
+            editor_cursor_char_bg: c(cursor),
+            editor_cursor_char_fg: c(bg),
+            editor_cursor_line_bg: c(mix(bg, fg, 0.08)),
+            editor_gutter_added: c(green),
+            editor_gutter_modified: c(yellow),
+            editor_line_added_bg: c(mix(bg, green, 0.12)),
+            editor_line_modified_bg: c(mix(bg, yellow, 0.12)),
+
+            file_tree_added: c(green),
+            file_tree_deleted: c(red),
+            file_tree_modified: c(yellow),
+            file_tree_renamed: c(blue),
+            file_tree_directory: c(blue),
+            file_tree_current_file_bg: c(selection),
+            file_tree_current_file_fg: c(fg),
+            file_tree_default: c(fg),
+            file_tree_stats_added: c(green),
+            file_tree_stats_deleted: c(red),
+
+            terminal_command: c(fg),
+            terminal_output: c(muted),
+            terminal_cursor_bg: c(cursor),
+            terminal_cursor_fg: c(bg),
+
+            status_hash: c(yellow),
+            status_author: c(green),
+            status_date: c(blue),
+            status_message: c(fg),
+            status_no_commit: c(muted),
+
+            separator: c(muted),
+
+            syntax_keyword: c(magenta),
+            syntax_type: c(cyan),
+            syntax_function: c(blue),
+            syntax_variable: c(fg),
+            syntax_string: c(green),
+            syntax_number: c(bright(3)),
+            syntax_comment: c(muted),
+            syntax_operator: c(cyan),
+            syntax_punctuation: c(mix(fg, bg, 0.3)),
+            syntax_constant: c(bright(3)),
+            syntax_parameter: c(red),
+            syntax_property: c(bright(4)),
+            syntax_label: c(magenta),
+
+            syntax_overrides: SyntaxOverrides::default(),
+        }
+    }
+}
+
+/// Set theme colors by field name
+fn apply(theme: &mut Theme, colors: &[(&str, Option<Rgb>)]) {
+    for (field, rgb) in colors {
+        if let Some([r, g, b]) = rgb {
+            theme.set_color(field, Color::Rgb(*r, *g, *b));
+        }
+    }
+}
+
+/// VS Code color theme: workbench `colors` and TextMate `tokenColors`
+fn vscode(text: &str) -> Result<Theme> {
+    let json: Value =
+        serde_json::from_str(&strip_jsonc(text)).context("Failed to parse VS Code theme JSON")?;
+
+    let rules = token_rules(&json);
+    // Rules without a scope hold the editor defaults in older themes
+    let global = |key: &str| {
+        json["tokenColors"]
+            .as_array()?
+            .iter()
+            .filter(|rule| rule.get("scope").is_none())
+            .find_map(|rule| rule["settings"][key].as_str())
+            .and_then(|value| parse_color(value, [0, 0, 0]))
+    };
+    let raw = |key: &str| json["colors"][key].as_str();
+    let background = raw("editor.background")
+        .and_then(|value| parse_color(value, [0, 0, 0]))
+        .or_else(|| global("background"));
+
+    let bg = background.unwrap_or_default();
+    let ui = |key: &str| raw(key).and_then(|value| parse_color(value, bg));
+    let token = |scopes: &[&str]| token_color(&rules, scopes);
+
+    let mut palette = Palette::new(
+        background,
+        ui("editor.foreground").or_else(|| global("foreground")),
+    )?;
+    // Fall back to token colors for the ANSI colors a theme doesn't set
+    let fallbacks: [&[&str]; 8] = [
+        &[],
+        &["entity.name.tag", "invalid"],
+        &["string"],
+        &["entity.name.type", "support.type"],
+        &["entity.name.function"],
+        &["keyword", "storage"],
+        &["support.function", "constant.character.escape"],
+        &[],
+    ];
+    for (i, name) in ANSI_NAMES.iter().enumerate() {
+        let title = capitalize(name);
+        palette.ansi[i] = ui(&format!("terminal.ansi{}", title)).or_else(|| token(fallbacks[i]));
+        palette.ansi[i + 8] = ui(&format!("terminal.ansiBright{}", title));
+    }
+    palette.ansi[8] = palette.ansi[8].or_else(|| token(&["comment"]));
+    palette.selection = ui("list.activeSelectionBackground").or(ui("editor.selectionBackground"));
+    palette.cursor = ui("editorCursor.foreground");
+
+    let mut theme = palette.theme();
+    apply(
+        &mut theme,
+        &[
+            ("background_left", ui("sideBar.background")),
+            ("editor_line_number", ui("editorLineNumber.foreground")),
+            (
+                "editor_line_number_cursor",
+                ui("editorLineNumber.activeForeground"),
+            ),
+            ("editor_separator", ui("editorGroup.border")),
+            (
+                "editor_cursor_line_bg",
+                ui("editor.lineHighlightBackground"),
+            ),
+            ("editor_gutter_added", ui("editorGutter.addedBackground")),
+            (
+                "editor_gutter_modified",
+                ui("editorGutter.modifiedBackground"),
+            ),
+            (
+                "editor_line_added_bg",
+                ui("diffEditor.insertedLineBackground"),
+            ),
+            (
+                "file_tree_added",
+                ui("gitDecoration.addedResourceForeground"),
+            ),
+            (
+                "file_tree_deleted",
+                ui("gitDecoration.deletedResourceForeground"),
+            ),
+            (
+                "file_tree_modified",
+                ui("gitDecoration.modifiedResourceForeground"),
+            ),
+            (
+                "file_tree_renamed",
+                ui("gitDecoration.renamedResourceForeground"),
+            ),
+            (
+                "file_tree_current_file_fg",
+                ui("list.activeSelectionForeground"),
+            ),
+            ("file_tree_default", ui("sideBar.foreground")),
+            (
+                "file_tree_stats_added",
+                ui("gitDecoration.addedResourceForeground"),
+            ),
+            (
+                "file_tree_stats_deleted",
+                ui("gitDecoration.deletedResourceForeground"),
+            ),
+            ("terminal_command", ui("terminal.foreground")),
+            ("terminal_cursor_bg", ui("terminalCursor.foreground")),
+            (
+                "separator",
+                ui("sideBar.border").or(ui("editorGroup.border")),
+            ),
+            (
+                "syntax_keyword",
+                token(&["keyword.control", "keyword", "storage"]),
+            ),
+            (
+                "syntax_type",
+                token(&["entity.name.type", "support.type", "entity.name.class"]),
+            ),
+            (
+                "syntax_function",
+                token(&["entity.name.function", "support.function"]),
+            ),
+            ("syntax_variable", token(&["variable.other", "variable"])),
+            ("syntax_string", token(&["string"])),
+            ("syntax_number", token(&["constant.numeric"])),
+            ("syntax_comment", token(&["comment"])),
+            ("syntax_operator", token(&["keyword.operator"])),
+            ("syntax_punctuation", token(&["punctuation"])),
+            (
+                "syntax_constant",
+                token(&["constant.language", "variable.other.constant", "constant"]),
+            ),
+            ("syntax_parameter", token(&["variable.parameter"])),
+            (
+                "syntax_property",
+                token(&["variable.other.property", "support.type.property-name"]),
+            ),
+            (
+                "syntax_label",
+                token(&["entity.name.label", "entity.name.tag"]),
+            ),
+            ("syntax_attribute", token(&["entity.other.attribute-name"])),
+            (
+                "syntax_builtin",
+                token(&["support.function.builtin", "variable.language"]),
+            ),
+            (
+                "syntax_comment_doc",
+                token(&["comment.block.documentation"]),
+            ),
+            ("syntax_macro", token(&["entity.name.function.macro"])),
+            ("syntax_markup_heading", token(&["markup.heading"])),
+            ("syntax_markup_link", token(&["markup.underline.link"])),
+            ("syntax_markup_raw", token(&["markup.inline.raw"])),
+            ("syntax_module", token(&["entity.name.namespace"])),
+            (
+                "syntax_string_escape",
+                token(&["constant.character.escape"]),
+            ),
+            ("syntax_string_special", token(&["string.regexp"])),
+            ("syntax_tag", token(&["entity.name.tag"])),
+        ],
+    );
+    Ok(theme)
+}
+
+/// A `tokenColors` entry with its scope selectors
+struct TokenRule {
+    selectors: Vec<String>,
+    foreground: Rgb,
+}
+
+fn token_rules(json: &Value) -> Vec<TokenRule> {
+    let background = json["colors"]["editor.background"]
+        .as_str()
+        .and_then(|value| parse_color(value, [0, 0, 0]))
+        .unwrap_or_default();
+    let Some(rules) = json["tokenColors"].as_array() else {
+        return Vec::new();
+    };
+
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let foreground = parse_color(rule["settings"]["foreground"].as_str()?, background)?;
+            let scopes: Vec<&str> = match &rule["scope"] {
+                Value::String(scope) => scope.split(',').collect(),
+                Value::Array(scopes) => scopes.iter().filter_map(Value::as_str).collect(),
+                _ => return None,
+            };
+            let selectors = scopes
+                .iter()
+                .map(|scope| scope.trim())
+                // Descendant selectors only apply in a context gitlogue doesn't have
+                .filter(|scope| !scope.is_empty() && !scope.contains(' '))
+                .map(String::from)
+                .collect();
+            Some(TokenRule {
+                selectors,
+                foreground,
+            })
+        })
+        .collect()
+}
+
+/// Color of the first scope any rule matches, preferring the most specific selector
+/// and the later of equally specific ones, as TextMate does
+fn token_color(rules: &[TokenRule], scopes: &[&str]) -> Option<Rgb> {
+    scopes.iter().find_map(|scope| {
+        let mut best: Option<(usize, Rgb)> = None;
+        for rule in rules {
+            for selector in &rule.selectors {
+                let matches = scope == selector
+                    || scope
+                        .strip_prefix(selector.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'));
+                let specificity = selector.split('.').count();
+                if matches && best.is_none_or(|(best, _)| specificity >= best) {
+                    best = Some((specificity, rule.foreground));
+                }
+            }
+        }
+        best.map(|(_, color)| color)
+    })
+}
+
+/// base16 scheme: `base00` to `base0F`, mapped following the base16 styling guidelines
+fn base16(keys: &BTreeMap<String, String>) -> Result<Theme> {
+    let mut base = [[0; 3]; 16];
+    for (i, color) in base.iter_mut().enumerate() {
+        let name = format!("base{:02X}", i);
+        let value = keys
+            .iter()
+            .find(|(key, _)| key.rsplit('.').next() == Some(&name))
+            .map(|(_, value)| value)
+            .with_context(|| format!("Missing {} in base16 scheme", name))?;
+        *color = parse_color(value, [0, 0, 0])
+            .with_context(|| format!("Invalid color for {}: {}", name, value))?;
+    }
+
+    let mut palette = Palette::new(Some(base[0x00]), Some(base[0x05]))?;
+    // Same slots as base16-shell
+    let ansi = [
+        0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x05, 0x03, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C,
+        0x07,
+    ];
+    palette.ansi = ansi.map(|i| Some(base[i]));
+    palette.selection = Some(base[0x02]);
+
+    let mut theme = palette.theme();
+    apply(
+        &mut theme,
+        &[
+            ("background_left", Some(base[0x01])),
+            ("editor_cursor_line_bg", Some(base[0x01])),
+            ("file_tree_modified", Some(base[0x0E])),
+            ("editor_gutter_modified", Some(base[0x0E])),
+            (
+                "editor_line_modified_bg",
+                Some(mix(base[0x00], base[0x0E], 0.12)),
+            ),
+            ("terminal_output", Some(base[0x04])),
+            ("syntax_type", Some(base[0x0A])),
+            ("syntax_variable", Some(base[0x08])),
+            ("syntax_number", Some(base[0x09])),
+            ("syntax_operator", Some(base[0x05])),
+            ("syntax_punctuation", Some(base[0x05])),
+            ("syntax_constant", Some(base[0x09])),
+            ("syntax_parameter", Some(base[0x08])),
+            ("syntax_property", Some(base[0x0C])),
+            ("syntax_label", Some(base[0x0A])),
+        ],
+    );
+    Ok(theme)
+}
+
+/// Alacritty `[colors]` from a TOML or legacy YAML config
+fn alacritty(keys: &BTreeMap<String, String>) -> Result<Theme> {
+    let color = |key: &str| {
+        keys.get(&format!("colors.{}", key))
+            .and_then(|value| parse_color(value, [0, 0, 0]))
+    };
+    let mut palette = Palette::new(color("primary.background"), color("primary.foreground"))
+        .context("Not an Alacritty color config")?;
+    for (i, name) in ANSI_NAMES.iter().enumerate() {
+        palette.ansi[i] = color(&format!("normal.{}", name));
+        palette.ansi[i + 8] = color(&format!("bright.{}", name));
+    }
+    palette.selection = color("selection.background");
+    palette.cursor = color("cursor.cursor");
+    Ok(palette.theme())
+}
+
+/// kitty color config: `name #rrggbb` lines
+fn kitty(text: &str) -> Result<Theme> {
+    let keys: BTreeMap<&str, Rgb> = text
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let key = parts.next()?;
+            Some((key, parse_color(parts.next()?, [0, 0, 0])?))
+        })
+        .collect();
+    let color = |key: &str| keys.get(key).copied();
+
+    let mut palette = Palette::new(color("background"), color("foreground"))
+        .context("Not a kitty color config")?;
+    for (i, slot) in palette.ansi.iter_mut().enumerate() {
+        *slot = color(&format!("color{}", i));
+    }
+    palette.selection = color("selection_background");
+    palette.cursor = color("cursor");
+    Ok(palette.theme())
+}
+
+/// Parse `#rgb`, `#rrggbb`, `#rrggbbaa` or `0xrrggbb`, blending translucent colors
+/// onto `background`
+fn parse_color(value: &str, background: Rgb) -> Option<Rgb> {
+    let value = value.trim();
+    let digits = value
+        .strip_prefix('#')
+        .or_else(|| value.strip_prefix("0x"))
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+
+    match digits.len() {
+        3 => {
+            let short = |i: usize| u8::from_str_radix(&digits[i..=i], 16).ok().map(|c| c * 17);
+            Some([short(0)?, short(1)?, short(2)?])
+        }
+        6 => Some([channel(0)?, channel(2)?, channel(4)?]),
+        8 => {
+            let alpha = f32::from(channel(6)?) / 255.0;
+            Some(mix(
+                background,
+                [channel(0)?, channel(2)?, channel(4)?],
+                alpha,
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn mix(from: Rgb, to: Rgb, amount: f32) -> Rgb {
+    let channel = |i: usize| {
+        (f32::from(from[i]) + (f32::from(to[i]) - f32::from(from[i])) * amount).round() as u8
+    };
+    [channel(0), channel(1), channel(2)]
+}
+
+fn hex([r, g, b]: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Theme name from a file name, e.g. `Dracula-color-theme` becomes `dracula`
+fn theme_name(stem: &str) -> String {
+    let name: String = stem
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+    let name = name
+        .strip_suffix("-color-theme")
+        .or_else(|| name.strip_suffix("-theme"))
+        .unwrap_or(name);
+    if name.is_empty() {
+        "imported".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// VS Code theme files are JSON with comments and trailing commas
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push(c);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ',' => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Scalar values of a simple YAML document keyed by their dotted path
+fn flatten_yaml(text: &str) -> BTreeMap<String, String> {
+    let unquote = |s: &str| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+    let mut keys = BTreeMap::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+
+    for line in text.lines() {
+        let content = line.trim_start();
+        if content.is_empty() || content.starts_with('#') || content.starts_with('-') {
+            continue;
+        }
+        let Some((key, value)) = content.split_once(':') else {
+            continue;
+        };
+        let indent = line.len() - content.len();
+        while parents.last().is_some_and(|(depth, _)| *depth >= indent) {
+            parents.pop();
+        }
+
+        let key = unquote(key);
+        // Values end at their closing quote, or at a comment if unquoted
+        let value = value.trim();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value.split(" #").next().unwrap_or_default(),
+        };
+        if value.is_empty() {
+            parents.push((indent, key));
+        } else {
+            let path: Vec<&str> = parents
+                .iter()
+                .map(|(_, parent)| parent.as_str())
+                .chain([key.as_str()])
+                .collect();
+            keys.insert(path.join("."), unquote(value));
+        }
+    }
+    keys
+}
+
+fn flatten_toml(value: &toml::Value, prefix: &str, keys: &mut BTreeMap<String, String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_toml(value, &path, keys);
+            }
+        }
+        toml::Value::String(value) => {
+            keys.insert(prefix.to_string(), value.clone());
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(color: Color) -> Rgb {
+        match color {
+            Color::Rgb(r, g, b) => [r, g, b],
+            other => panic!("not an RGB color: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_vscode_theme_maps_scopes_and_ui_colors() {
+        let text = r##"{
+            // Comments and trailing commas are allowed
+            "colors": {
+                "editor.background": "#282a36",
+                "editor.foreground": "#f8f8f2",
+                "editor.lineHighlightBackground": "#ffffff20",
+                "gitDecoration.addedResourceForeground": "#50fa7b",
+            },
+            "tokenColors": [
+                { "scope": "keyword", "settings": { "foreground": "#ff79c6" } },
+                { "scope": ["keyword.operator", "string"], "settings": { "foreground": "#f1fa8c" } },
+                { "scope": "meta.function keyword", "settings": { "foreground": "#000000" } },
+                { "scope": "comment, punctuation.definition.comment", "settings": { "foreground": "#6272a4" } },
+            ],
+        }"##;
+        let theme = vscode(text).unwrap();
+
+        assert_eq!(rgb(theme.background_right), [0x28, 0x2a, 0x36]);
+        assert_eq!(rgb(theme.syntax_keyword), [0xff, 0x79, 0xc6]);
+        assert_eq!(rgb(theme.syntax_operator), [0xf1, 0xfa, 0x8c]);
+        assert_eq!(rgb(theme.syntax_comment), [0x62, 0x72, 0xa4]);
+        assert_eq!(rgb(theme.file_tree_added), [0x50, 0xfa, 0x7b]);
+        // 0x20 alpha over the background
+        assert_eq!(rgb(theme.editor_cursor_line_bg), [0x43, 0x45, 0x4f]);
+    }
+
+    #[test]
+    fn test_base16_scheme_follows_styling_guidelines() {
+        let mut text = String::from("scheme: \"Test\"\nauthor: \"me\"\n");
+        for i in 0..16 {
+            text.push_str(&format!(
+                "base{:02X}: \"{:02x}{:02x}{:02x}\" # comment\n",
+                i, i, i, i
+            ));
+        }
+        let theme = base16(&flatten_yaml(&text)).unwrap();
+
+        assert_eq!(rgb(theme.background_right), [0; 3]);
+        assert_eq!(rgb(theme.syntax_keyword), [0x0e; 3]);
+        assert_eq!(rgb(theme.syntax_number), [0x09; 3]);
+        assert_eq!(rgb(theme.syntax_comment), [0x03; 3]);
+
+        let error = base16(&flatten_yaml("base00: \"000000\"\n")).unwrap_err();
+        assert!(error.to_string().contains("Missing base01"));
+    }
+
+    #[test]
+    fn test_terminal_configs_use_ansi_colors() {
+        let yaml = "colors:\n  primary:\n    background: '0x1d1f21'\n    foreground: '0xc5c8c6'\n  normal:\n    green: '0xb5bd68'\n    magenta: '0xb294bb'\n";
+        let theme = alacritty(&flatten_yaml(yaml)).unwrap();
+        assert_eq!(rgb(theme.background_right), [0x1d, 0x1f, 0x21]);
+        assert_eq!(rgb(theme.syntax_string), [0xb5, 0xbd, 0x68]);
+        assert_eq!(rgb(theme.syntax_keyword), [0xb2, 0x94, 0xbb]);
+        // Unset colors fall back to the foreground
+        assert_eq!(rgb(theme.syntax_function), [0xc5, 0xc8, 0xc6]);
+
+        let mut keys = BTreeMap::new();
+        let toml: toml::Value = toml::from_str(
+            "[colors.primary]\nbackground = \"#1d1f21\"\nforeground = \"#c5c8c6\"\n",
+        )
+        .unwrap();
+        flatten_toml(&toml, "", &mut keys);
+        assert!(alacritty(&keys).is_ok());
+
+        let conf = "# kitty\nforeground #c5c8c6\nbackground #1d1f21\ncolor1 #cc6666\n";
+        let theme = kitty(conf).unwrap();
+        assert_eq!(rgb(theme.file_tree_deleted), [0xcc, 0x66, 0x66]);
+        assert!(kitty("font_size 12\n").is_err());
+    }
+
+    #[test]
+    fn test_theme_name_from_file_name() {
+        assert_eq!(theme_name("Dracula-color-theme"), "dracula");
+        assert_eq!(theme_name("base16 Ocean"), "base16-ocean");
+        assert_eq!(theme_name("..."), "imported");
+    }
+}
@@ -1,7 +1,18 @@
+mod color;
+mod contrast;
+mod import;
 mod themes;
+mod user;
 
-use anyhow::{Context, Result};
-use ratatui::style::Color;
+pub use color::{blend, ColorMode};
+pub use contrast::AA_CONTRAST;
+pub use import::import_theme;
+pub use user::{init_themes_dir, user_themes};
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use ratatui::style::{Color, Modifier, Style};
 
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -16,6 +27,10 @@ pub struct Theme {
     pub editor_cursor_char_bg: Color,
     pub editor_cursor_char_fg: Color,
     pub editor_cursor_line_bg: Color,
+    pub editor_gutter_added: Color,
+    pub editor_gutter_modified: Color,
+    pub editor_line_added_bg: Color,
+    pub editor_line_modified_bg: Color,
 
     // File tree colors
     pub file_tree_added: Color,
@@ -59,6 +74,222 @@ pub struct Theme {
     pub syntax_parameter: Color,
     pub syntax_property: Color,
     pub syntax_label: Color,
+
+    // Finer syntax categories, font styles and per-language styles, set by theme files
+    pub syntax_overrides: SyntaxOverrides,
+}
+
+/// Opacity of the added and modified colors in derived change marker line backgrounds
+const MARKER_TINT: f32 = 0.12;
+
+/// Syntax styles a theme may leave unset, falling back to the broader categories
+#[derive(Debug, Clone, Default)]
+pub struct SyntaxOverrides {
+    /// Colors of `Theme::OPTIONAL_FIELDS` and font styles of any syntax field, by field name
+    styles: BTreeMap<String, Style>,
+    /// Syntax styles for a single language, by language name and field name
+    languages: BTreeMap<String, BTreeMap<String, Style>>,
+}
+
+/// Lists every color field so theme files can set them by name
+macro_rules! color_fields {
+    ($($field:ident),* $(,)?) => {
+        impl Theme {
+            /// Names of the color fields every theme sets, as used in theme files
+            pub const FIELDS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            /// Color by field name, or `None` for unknown names and unset optional colors
+            fn color(&self, name: &str) -> Option<Color> {
+                match name {
+                    $(stringify!($field) => Some(self.$field),)*
+                    _ => self.syntax_overrides.styles.get(name).and_then(|style| style.fg),
+                }
+            }
+
+            fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+                match name {
+                    $(stringify!($field) => Some(&mut self.$field),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+color_fields!(
+    background_left,
+    background_right,
+    editor_line_number,
+    editor_line_number_cursor,
+    editor_separator,
+    editor_cursor_char_bg,
+    editor_cursor_char_fg,
+    editor_cursor_line_bg,
+    editor_gutter_added,
+    editor_gutter_modified,
+    editor_line_added_bg,
+    editor_line_modified_bg,
+    file_tree_added,
+    file_tree_deleted,
+    file_tree_modified,
+    file_tree_renamed,
+    file_tree_directory,
+    file_tree_current_file_bg,
+    file_tree_current_file_fg,
+    file_tree_default,
+    file_tree_stats_added,
+    file_tree_stats_deleted,
+    terminal_command,
+    terminal_output,
+    terminal_cursor_bg,
+    terminal_cursor_fg,
+    status_hash,
+    status_author,
+    status_date,
+    status_message,
+    status_no_commit,
+    separator,
+    syntax_keyword,
+    syntax_type,
+    syntax_function,
+    syntax_variable,
+    syntax_string,
+    syntax_number,
+    syntax_comment,
+    syntax_operator,
+    syntax_punctuation,
+    syntax_constant,
+    syntax_parameter,
+    syntax_property,
+    syntax_label,
+);
+
+impl Theme {
+    /// Syntax colors for finer token categories, unset in the built-in themes
+    pub const OPTIONAL_FIELDS: &'static [&'static str] = &[
+        "syntax_attribute",
+        "syntax_boolean",
+        "syntax_builtin",
+        "syntax_comment_doc",
+        "syntax_constructor",
+        "syntax_macro",
+        "syntax_markup_heading",
+        "syntax_markup_link",
+        "syntax_markup_raw",
+        "syntax_method",
+        "syntax_module",
+        "syntax_string_escape",
+        "syntax_string_special",
+        "syntax_tag",
+    ];
+
+    /// Colors of the editor's change markers, which theme files may leave out to derive
+    /// them from the file tree colors
+    const MARKER_FIELDS: &'static [&'static str] = &[
+        "editor_gutter_added",
+        "editor_gutter_modified",
+        "editor_line_added_bg",
+        "editor_line_modified_bg",
+    ];
+
+    /// Change marker color derived from the file tree's added and modified colors, with
+    /// the line backgrounds a faint tint of them
+    fn derived_marker_color(&self, name: &str) -> Option<Color> {
+        let tint = |color| blend(color, self.background_right, MARKER_TINT);
+        match name {
+            "editor_gutter_added" => Some(self.file_tree_added),
+            "editor_gutter_modified" => Some(self.file_tree_modified),
+            "editor_line_added_bg" => tint(self.file_tree_added),
+            "editor_line_modified_bg" => tint(self.file_tree_modified),
+            _ => None,
+        }
+    }
+
+    /// Colors a derived change marker color is made from
+    fn marker_inputs(name: &str) -> &'static [&'static str] {
+        match name {
+            "editor_gutter_added" => &["file_tree_added"],
+            "editor_gutter_modified" => &["file_tree_modified"],
+            "editor_line_added_bg" => &["file_tree_added", "background_right"],
+            "editor_line_modified_bg" => &["file_tree_modified", "background_right"],
+            _ => &[],
+        }
+    }
+
+    /// Set a color by field name, returning `false` for unknown names
+    fn set_color(&mut self, name: &str, color: Color) -> bool {
+        if let Some(field) = self.color_mut(name) {
+            *field = color;
+        } else if Self::OPTIONAL_FIELDS.contains(&name) {
+            let style = self
+                .syntax_overrides
+                .styles
+                .entry(name.to_string())
+                .or_default();
+            style.fg = Some(color);
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn has_field(name: &str) -> bool {
+        Self::FIELDS.contains(&name) || Self::OPTIONAL_FIELDS.contains(&name)
+    }
+
+    fn is_syntax_field(name: &str) -> bool {
+        name.starts_with("syntax_") && Self::has_field(name)
+    }
+
+    /// Add font styles such as bold or italic to a syntax field, returning `false` for
+    /// fields that aren't syntax colors
+    fn set_font_style(&mut self, name: &str, font_style: Style) -> bool {
+        let is_syntax = Self::is_syntax_field(name);
+        if is_syntax {
+            let style = self
+                .syntax_overrides
+                .styles
+                .entry(name.to_string())
+                .or_default();
+            *style = style.patch(font_style);
+        }
+        is_syntax
+    }
+
+    /// Set a syntax style for files in one language only
+    fn set_language_style(&mut self, language: &str, name: &str, language_style: Style) -> bool {
+        let is_syntax = Self::is_syntax_field(name);
+        if is_syntax {
+            let style = self
+                .syntax_overrides
+                .languages
+                .entry(language.to_string())
+                .or_default()
+                .entry(name.to_string())
+                .or_default();
+            *style = style.patch(language_style);
+        }
+        is_syntax
+    }
+
+    /// Style of a syntax field, with the style set for the language on top. Unset
+    /// optional fields have an empty style.
+    pub fn syntax_style(&self, name: &str, language: Option<&str>) -> Style {
+        let mut style = self
+            .syntax_overrides
+            .styles
+            .get(name)
+            .copied()
+            .unwrap_or_default();
+        style.fg = self.color(name);
+        match language
+            .and_then(|language| self.syntax_overrides.languages.get(language))
+            .and_then(|styles| styles.get(name))
+        {
+            Some(language_style) => style.patch(*language_style),
+            None => style,
+        }
+    }
 }
 
 impl Default for Theme {
@@ -68,27 +299,47 @@ impl Default for Theme {
 }
 
 impl Theme {
-    /// Load theme by name
+    /// Load theme by name, preferring a user theme file over a built-in theme
     pub fn load(name: &str) -> Result<Self> {
+        if let Some(theme) = user::load(name)? {
+            return Ok(theme);
+        }
+        Self::builtin(name).ok_or_else(|| {
+            let mut available: Vec<String> = Self::available_themes()
+                .into_iter()
+                .map(String::from)
+                .collect();
+            available.extend(user_themes());
+            anyhow::anyhow!("Unknown theme: {}", name)
+                .context(format!("Available themes: {}", available.join(", ")))
+        })
+    }
+
+    /// Load a theme file by path; `extends` looks in the file's directory first
+    pub fn load_file(path: &std::path::Path) -> Result<Self> {
+        user::load_file(path)
+    }
+
+    /// Built-in theme by name
+    fn builtin(name: &str) -> Option<Self> {
         match name {
-            "ayu-dark" => Ok(themes::ayu_dark()),
-            "catppuccin" => Ok(themes::catppuccin()),
-            "dracula" => Ok(themes::dracula()),
-            "everforest" => Ok(themes::everforest()),
-            "github-dark" => Ok(themes::github_dark()),
-            "gruvbox" => Ok(themes::gruvbox()),
-            "material" => Ok(themes::material()),
-            "monokai" => Ok(themes::monokai()),
-            "night-owl" => Ok(themes::night_owl()),
-            "nord" => Ok(themes::nord()),
-            "one-dark" => Ok(themes::one_dark()),
-            "rose-pine" => Ok(themes::rose_pine()),
-            "solarized-dark" => Ok(themes::solarized_dark()),
-            "solarized-light" => Ok(themes::solarized_light()),
-            "telemetry" => Ok(themes::telemetry()),
-            "tokyo-night" => Ok(themes::tokyo_night()),
-            _ => Err(anyhow::anyhow!("Unknown theme: {}", name))
-                .context("Available themes: ayu-dark, catppuccin, dracula, everforest, github-dark, gruvbox, material, monokai, night-owl, nord, one-dark, rose-pine, solarized-dark, solarized-light, telemetry, tokyo-night"),
+            "ayu-dark" => Some(themes::ayu_dark()),
+            "catppuccin" => Some(themes::catppuccin()),
+            "dracula" => Some(themes::dracula()),
+            "everforest" => Some(themes::everforest()),
+            "github-dark" => Some(themes::github_dark()),
+            "gruvbox" => Some(themes::gruvbox()),
+            "material" => Some(themes::material()),
+            "monokai" => Some(themes::monokai()),
+            "night-owl" => Some(themes::night_owl()),
+            "nord" => Some(themes::nord()),
+            "one-dark" => Some(themes::one_dark()),
+            "rose-pine" => Some(themes::rose_pine()),
+            "solarized-dark" => Some(themes::solarized_dark()),
+            "solarized-light" => Some(themes::solarized_light()),
+            "telemetry" => Some(themes::telemetry()),
+            "tokyo-night" => Some(themes::tokyo_night()),
+            _ => None,
         }
     }
 
@@ -99,6 +350,68 @@ impl Theme {
         self
     }
 
+    /// Style for a selected line. A highlight on the terminal's default background, as
+    /// with colors off, would be invisible, so it is drawn in reverse video instead.
+    pub fn highlight(bg: Color) -> Style {
+        match bg {
+            Color::Reset => Style::default().add_modifier(Modifier::REVERSED),
+            bg => Style::default().bg(bg),
+        }
+    }
+
+    /// Style of the editor's cursor character, drawn on the cursor line's highlight
+    pub fn cursor_style(&self) -> Style {
+        let style = Style::default()
+            .bg(self.editor_cursor_char_bg)
+            .fg(self.editor_cursor_char_fg)
+            .add_modifier(Modifier::BOLD);
+        match (self.editor_cursor_char_bg, self.editor_cursor_line_bg) {
+            // Stand out from a reversed cursor line by flipping back
+            (Color::Reset, Color::Reset) => style.remove_modifier(Modifier::REVERSED),
+            (Color::Reset, _) => style.add_modifier(Modifier::REVERSED),
+            _ => style,
+        }
+    }
+
+    /// Convert every color to the palette the terminal supports
+    pub fn with_color_mode(mut self, mode: ColorMode) -> Self {
+        let mode = mode.resolve();
+        for field in Self::FIELDS {
+            if let Some(color) = self.color_mut(field) {
+                *color = mode.convert(*color);
+            }
+        }
+        let overrides = &mut self.syntax_overrides;
+        let language_styles = overrides
+            .languages
+            .values_mut()
+            .flat_map(|styles| styles.values_mut());
+        for style in overrides.styles.values_mut().chain(language_styles) {
+            style.fg = style.fg.map(|color| mode.convert(color));
+        }
+        self
+    }
+
+    /// A theme part way from this one to `other`, for cross-fading between them. Colors
+    /// that can't be blended, and styles only one of the themes sets, switch halfway.
+    pub fn mix(&self, other: &Theme, amount: f32) -> Theme {
+        let mut mixed = if amount < 0.5 {
+            self.clone()
+        } else {
+            other.clone()
+        };
+        for name in Self::FIELDS.iter().chain(Self::OPTIONAL_FIELDS) {
+            let blended = match (self.color(name), other.color(name)) {
+                (Some(from), Some(to)) => blend(to, from, amount),
+                _ => None,
+            };
+            if let Some(color) = blended {
+                mixed.set_color(name, color);
+            }
+        }
+        mixed
+    }
+
     /// List all available built-in themes
     pub fn available_themes() -> Vec<&'static str> {
         vec![
@@ -121,3 +434,57 @@ impl Theme {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_blends_colors_between_themes() {
+        let mut from = Theme::default();
+        let mut to = Theme::default();
+        from.background_right = Color::Rgb(0, 0, 0);
+        to.background_right = Color::Rgb(200, 100, 50);
+        to.separator = Color::Red;
+        to.set_color("syntax_macro", Color::Rgb(255, 0, 0));
+
+        let start = from.mix(&to, 0.0);
+        assert_eq!(start.background_right, Color::Rgb(0, 0, 0));
+        assert_eq!(start.color("syntax_macro"), None);
+
+        let quarter = from.mix(&to, 0.25);
+        assert_eq!(quarter.background_right, Color::Rgb(50, 25, 12));
+        assert_eq!(quarter.separator, from.separator);
+
+        let three_quarters = from.mix(&to, 0.75);
+        assert_eq!(three_quarters.separator, Color::Red);
+        assert_eq!(
+            three_quarters.color("syntax_macro"),
+            Some(Color::Rgb(255, 0, 0))
+        );
+
+        assert_eq!(from.mix(&to, 1.0).background_right, to.background_right);
+    }
+
+    #[test]
+    fn test_highlights_use_reverse_video_without_colors() {
+        let colored = Theme::default();
+        assert_eq!(
+            Theme::highlight(colored.editor_cursor_line_bg),
+            Style::default().bg(colored.editor_cursor_line_bg)
+        );
+        assert!(!colored
+            .cursor_style()
+            .add_modifier
+            .contains(Modifier::REVERSED));
+
+        let plain = Theme::default().with_color_mode(ColorMode::None);
+        let line = Theme::highlight(plain.editor_cursor_line_bg);
+        assert!(line.add_modifier.contains(Modifier::REVERSED));
+        // The cursor flips back out of the reversed cursor line
+        assert!(plain
+            .cursor_style()
+            .sub_modifier
+            .contains(Modifier::REVERSED));
+    }
+}
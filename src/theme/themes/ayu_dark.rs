@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Ayu Dark inspired color scheme
@@ -7,12 +7,16 @@ pub fn ayu_dark() -> Theme {
         background_left: Color::Rgb(10, 14, 20),
         background_right: Color::Rgb(15, 20, 25),
 
-        editor_line_number: Color::Rgb(62, 68, 82),
+        editor_line_number: Color::Rgb(65, 71, 85),
         editor_line_number_cursor: Color::Rgb(89, 182, 215),
         editor_separator: Color::Rgb(62, 68, 82),
         editor_cursor_char_bg: Color::Rgb(255, 180, 84),
         editor_cursor_char_fg: Color::Rgb(15, 20, 25),
         editor_cursor_line_bg: Color::Rgb(22, 29, 37),
+        editor_gutter_added: Color::Rgb(186, 230, 126),
+        editor_gutter_modified: Color::Rgb(255, 180, 84),
+        editor_line_added_bg: Color::Rgb(35, 45, 37),
+        editor_line_modified_bg: Color::Rgb(43, 39, 32),
 
         file_tree_added: Color::Rgb(186, 230, 126),
         file_tree_deleted: Color::Rgb(242, 97, 103),
@@ -51,5 +55,7 @@ pub fn ayu_dark() -> Theme {
         syntax_parameter: Color::Rgb(255, 214, 111),
         syntax_property: Color::Rgb(115, 184, 205),
         syntax_label: Color::Rgb(255, 140, 99),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Catppuccin Mocha inspired color scheme
@@ -13,6 +13,10 @@ pub fn catppuccin() -> Theme {
         editor_cursor_char_bg: Color::Rgb(245, 194, 231),
         editor_cursor_char_fg: Color::Rgb(30, 30, 46),
         editor_cursor_line_bg: Color::Rgb(49, 50, 68),
+        editor_gutter_added: Color::Rgb(166, 227, 161),
+        editor_gutter_modified: Color::Rgb(250, 179, 135),
+        editor_line_added_bg: Color::Rgb(46, 53, 59),
+        editor_line_modified_bg: Color::Rgb(56, 47, 56),
 
         file_tree_added: Color::Rgb(166, 227, 161),
         file_tree_deleted: Color::Rgb(243, 139, 168),
@@ -51,5 +55,7 @@ pub fn catppuccin() -> Theme {
         syntax_parameter: Color::Rgb(245, 194, 231),
         syntax_property: Color::Rgb(166, 227, 161),
         syntax_label: Color::Rgb(203, 166, 247),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Dracula inspired color scheme
@@ -13,6 +13,10 @@ pub fn dracula() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 121, 198),
         editor_cursor_char_fg: Color::Rgb(40, 42, 54),
         editor_cursor_line_bg: Color::Rgb(68, 71, 90),
+        editor_gutter_added: Color::Rgb(80, 250, 123),
+        editor_gutter_modified: Color::Rgb(255, 184, 108),
+        editor_line_added_bg: Color::Rgb(44, 66, 62),
+        editor_line_modified_bg: Color::Rgb(65, 59, 60),
 
         file_tree_added: Color::Rgb(80, 250, 123),
         file_tree_deleted: Color::Rgb(255, 85, 85),
@@ -51,5 +55,7 @@ pub fn dracula() -> Theme {
         syntax_parameter: Color::Rgb(255, 184, 108),
         syntax_property: Color::Rgb(80, 250, 123),
         syntax_label: Color::Rgb(255, 121, 198),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Everforest Dark inspired color scheme
@@ -13,6 +13,10 @@ pub fn everforest() -> Theme {
         editor_cursor_char_bg: Color::Rgb(131, 192, 146),
         editor_cursor_char_fg: Color::Rgb(45, 52, 46),
         editor_cursor_line_bg: Color::Rgb(57, 64, 58),
+        editor_gutter_added: Color::Rgb(131, 192, 146),
+        editor_gutter_modified: Color::Rgb(219, 188, 127),
+        editor_line_added_bg: Color::Rgb(55, 68, 58),
+        editor_line_modified_bg: Color::Rgb(65, 68, 55),
 
         file_tree_added: Color::Rgb(131, 192, 146),
         file_tree_deleted: Color::Rgb(230, 126, 128),
@@ -51,5 +55,7 @@ pub fn everforest() -> Theme {
         syntax_parameter: Color::Rgb(219, 188, 127),
         syntax_property: Color::Rgb(125, 192, 192),
         syntax_label: Color::Rgb(230, 126, 128),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// GitHub Dark inspired color scheme
@@ -13,6 +13,10 @@ pub fn github_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(88, 166, 255),
         editor_cursor_char_fg: Color::Rgb(22, 27, 34),
         editor_cursor_line_bg: Color::Rgb(33, 38, 45),
+        editor_gutter_added: Color::Rgb(63, 185, 80),
+        editor_gutter_modified: Color::Rgb(219, 109, 40),
+        editor_line_added_bg: Color::Rgb(26, 45, 39),
+        editor_line_modified_bg: Color::Rgb(45, 36, 34),
 
         file_tree_added: Color::Rgb(63, 185, 80),
         file_tree_deleted: Color::Rgb(248, 81, 73),
@@ -51,5 +55,7 @@ pub fn github_dark() -> Theme {
         syntax_parameter: Color::Rgb(255, 186, 77),
         syntax_property: Color::Rgb(121, 192, 255),
         syntax_label: Color::Rgb(210, 153, 255),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Gruvbox Dark inspired color scheme
@@ -13,6 +13,10 @@ pub fn gruvbox() -> Theme {
         editor_cursor_char_bg: Color::Rgb(254, 128, 25),
         editor_cursor_char_fg: Color::Rgb(40, 40, 40),
         editor_cursor_line_bg: Color::Rgb(60, 56, 54),
+        editor_gutter_added: Color::Rgb(184, 187, 38),
+        editor_gutter_modified: Color::Rgb(254, 128, 25),
+        editor_line_added_bg: Color::Rgb(57, 57, 39),
+        editor_line_modified_bg: Color::Rgb(65, 50, 38),
 
         file_tree_added: Color::Rgb(184, 187, 38),
         file_tree_deleted: Color::Rgb(251, 73, 52),
@@ -51,5 +55,7 @@ pub fn gruvbox() -> Theme {
         syntax_parameter: Color::Rgb(254, 128, 25),
         syntax_property: Color::Rgb(184, 187, 38),
         syntax_label: Color::Rgb(251, 73, 52),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Material Theme inspired color scheme
@@ -13,6 +13,10 @@ pub fn material() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 203, 107),
         editor_cursor_char_fg: Color::Rgb(38, 50, 56),
         editor_cursor_line_bg: Color::Rgb(55, 71, 79),
+        editor_gutter_added: Color::Rgb(195, 232, 141),
+        editor_gutter_modified: Color::Rgb(255, 203, 107),
+        editor_line_added_bg: Color::Rgb(56, 71, 66),
+        editor_line_modified_bg: Color::Rgb(64, 68, 62),
 
         file_tree_added: Color::Rgb(195, 232, 141),
         file_tree_deleted: Color::Rgb(255, 83, 112),
@@ -51,5 +55,7 @@ pub fn material() -> Theme {
         syntax_parameter: Color::Rgb(255, 203, 107),
         syntax_property: Color::Rgb(128, 203, 196),
         syntax_label: Color::Rgb(199, 146, 234),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Monokai inspired color scheme
@@ -13,6 +13,10 @@ pub fn monokai() -> Theme {
         editor_cursor_char_bg: Color::Rgb(253, 151, 31),
         editor_cursor_char_fg: Color::Rgb(39, 40, 34),
         editor_cursor_line_bg: Color::Rgb(51, 51, 45),
+        editor_gutter_added: Color::Rgb(166, 226, 46),
+        editor_gutter_modified: Color::Rgb(253, 151, 31),
+        editor_line_added_bg: Color::Rgb(54, 62, 35),
+        editor_line_modified_bg: Color::Rgb(64, 53, 33),
 
         file_tree_added: Color::Rgb(166, 226, 46),
         file_tree_deleted: Color::Rgb(249, 38, 114),
@@ -51,5 +55,7 @@ pub fn monokai() -> Theme {
         syntax_parameter: Color::Rgb(253, 151, 31),
         syntax_property: Color::Rgb(166, 226, 46),
         syntax_label: Color::Rgb(249, 38, 114),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
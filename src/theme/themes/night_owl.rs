@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Night Owl inspired color scheme
@@ -13,6 +13,10 @@ pub fn night_owl() -> Theme {
         editor_cursor_char_bg: Color::Rgb(122, 162, 247),
         editor_cursor_char_fg: Color::Rgb(1, 22, 39),
         editor_cursor_line_bg: Color::Rgb(1, 41, 72),
+        editor_gutter_added: Color::Rgb(173, 219, 103),
+        editor_gutter_modified: Color::Rgb(255, 213, 128),
+        editor_line_added_bg: Color::Rgb(21, 45, 46),
+        editor_line_modified_bg: Color::Rgb(31, 44, 49),
 
         file_tree_added: Color::Rgb(173, 219, 103),
         file_tree_deleted: Color::Rgb(239, 83, 80),
@@ -51,5 +55,7 @@ pub fn night_owl() -> Theme {
         syntax_parameter: Color::Rgb(255, 203, 107),
         syntax_property: Color::Rgb(122, 162, 247),
         syntax_label: Color::Rgb(255, 88, 116),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
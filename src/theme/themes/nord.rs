@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Nord inspired color scheme
@@ -13,6 +13,10 @@ pub fn nord() -> Theme {
         editor_cursor_char_bg: Color::Rgb(136, 192, 208),
         editor_cursor_char_fg: Color::Rgb(46, 52, 64),
         editor_cursor_line_bg: Color::Rgb(59, 66, 82),
+        editor_gutter_added: Color::Rgb(163, 190, 140),
+        editor_gutter_modified: Color::Rgb(235, 203, 139),
+        editor_line_added_bg: Color::Rgb(44, 56, 54),
+        editor_line_modified_bg: Color::Rgb(58, 55, 50),
 
         file_tree_added: Color::Rgb(163, 190, 140),
         file_tree_deleted: Color::Rgb(191, 97, 106),
@@ -51,5 +55,7 @@ pub fn nord() -> Theme {
         syntax_parameter: Color::Rgb(235, 203, 139),
         syntax_property: Color::Rgb(163, 190, 140),
         syntax_label: Color::Rgb(180, 142, 173),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// One Dark inspired color scheme
@@ -13,6 +13,10 @@ pub fn one_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(97, 175, 239),
         editor_cursor_char_fg: Color::Rgb(40, 44, 52),
         editor_cursor_line_bg: Color::Rgb(47, 52, 61),
+        editor_gutter_added: Color::Rgb(152, 195, 121),
+        editor_gutter_modified: Color::Rgb(209, 154, 102),
+        editor_line_added_bg: Color::Rgb(53, 62, 60),
+        editor_line_modified_bg: Color::Rgb(60, 57, 58),
 
         file_tree_added: Color::Rgb(152, 195, 121),
         file_tree_deleted: Color::Rgb(224, 108, 117),
@@ -51,5 +55,7 @@ pub fn one_dark() -> Theme {
         syntax_parameter: Color::Rgb(229, 192, 123),
         syntax_property: Color::Rgb(152, 195, 121),
         syntax_label: Color::Rgb(198, 120, 221),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
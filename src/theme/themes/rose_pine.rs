@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Rose Pine inspired color scheme
@@ -13,6 +13,10 @@ pub fn rose_pine() -> Theme {
         editor_cursor_char_bg: Color::Rgb(235, 188, 186),
         editor_cursor_char_fg: Color::Rgb(35, 33, 54),
         editor_cursor_line_bg: Color::Rgb(42, 39, 63),
+        editor_gutter_added: Color::Rgb(156, 207, 216),
+        editor_gutter_modified: Color::Rgb(246, 193, 119),
+        editor_line_added_bg: Color::Rgb(49, 53, 73),
+        editor_line_modified_bg: Color::Rgb(60, 52, 61),
 
         file_tree_added: Color::Rgb(156, 207, 216),
         file_tree_deleted: Color::Rgb(235, 111, 146),
@@ -51,5 +55,7 @@ pub fn rose_pine() -> Theme {
         syntax_parameter: Color::Rgb(246, 193, 119),
         syntax_property: Color::Rgb(156, 207, 216),
         syntax_label: Color::Rgb(196, 167, 231),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Solarized Light color scheme
@@ -13,6 +13,10 @@ pub fn solarized_light() -> Theme {
         editor_cursor_char_bg: Color::Rgb(38, 139, 210),
         editor_cursor_char_fg: Color::Rgb(253, 246, 227),
         editor_cursor_line_bg: Color::Rgb(238, 232, 213),
+        editor_gutter_added: Color::Rgb(133, 153, 0),
+        editor_gutter_modified: Color::Rgb(181, 137, 0),
+        editor_line_added_bg: Color::Rgb(238, 234, 199),
+        editor_line_modified_bg: Color::Rgb(244, 232, 199),
 
         file_tree_added: Color::Rgb(133, 153, 0),
         file_tree_deleted: Color::Rgb(220, 50, 47),
@@ -51,5 +55,7 @@ pub fn solarized_light() -> Theme {
         syntax_parameter: Color::Rgb(181, 137, 0),
         syntax_property: Color::Rgb(42, 161, 152),
         syntax_label: Color::Rgb(211, 54, 130),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Telemetry - "Encrypt the signal; the void is listening."
@@ -14,6 +14,10 @@ pub fn telemetry() -> Theme {
         editor_cursor_char_bg: Color::Rgb(244, 174, 89),
         editor_cursor_char_fg: Color::Rgb(15, 27, 29),
         editor_cursor_line_bg: Color::Rgb(29, 46, 49),
+        editor_gutter_added: Color::Rgb(141, 172, 139),
+        editor_gutter_modified: Color::Rgb(244, 174, 89),
+        editor_line_added_bg: Color::Rgb(36, 50, 48),
+        editor_line_modified_bg: Color::Rgb(48, 50, 42),
 
         file_tree_added: Color::Rgb(141, 172, 139),
         file_tree_deleted: Color::Rgb(194, 113, 102),
@@ -52,5 +56,7 @@ pub fn telemetry() -> Theme {
         syntax_parameter: Color::Rgb(122, 154, 153),
         syntax_property: Color::Rgb(154, 191, 190),
         syntax_label: Color::Rgb(194, 113, 102),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
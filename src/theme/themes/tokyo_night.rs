@@ -1,4 +1,4 @@
-use super::super::Theme;
+use super::super::{SyntaxOverrides, Theme};
 use ratatui::style::Color;
 
 /// Tokyo Night inspired color scheme
@@ -13,6 +13,10 @@ pub fn tokyo_night() -> Theme {
         editor_cursor_char_bg: Color::Rgb(122, 162, 247),
         editor_cursor_char_fg: Color::Rgb(26, 27, 38),
         editor_cursor_line_bg: Color::Rgb(42, 47, 68),
+        editor_gutter_added: Color::Rgb(158, 206, 106),
+        editor_gutter_modified: Color::Rgb(255, 158, 100),
+        editor_line_added_bg: Color::Rgb(41, 48, 46),
+        editor_line_modified_bg: Color::Rgb(53, 42, 45),
 
         file_tree_added: Color::Rgb(158, 206, 106),
         file_tree_deleted: Color::Rgb(247, 118, 142),
@@ -51,5 +55,7 @@ pub fn tokyo_night() -> Theme {
         syntax_parameter: Color::Rgb(255, 213, 128),
         syntax_property: Color::Rgb(158, 206, 106),
         syntax_label: Color::Rgb(187, 154, 247),
+
+        syntax_overrides: SyntaxOverrides::default(),
     }
 }
@@ -0,0 +1,471 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use super::Theme;
+use crate::syntax::LANGUAGE_NAMES;
+
+static THEMES_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Theme files may extend each other; deeper chains are assumed to be cycles
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// Initialize the directory user theme files are loaded from (call once at startup)
+pub fn init_themes_dir(dir: PathBuf) -> Result<()> {
+    THEMES_DIR
+        .set(dir)
+        .map_err(|_| anyhow::anyhow!("Themes directory already initialized"))
+}
+
+/// Names of the user themes, from the `*.toml` files in the themes directory
+pub fn user_themes() -> Vec<String> {
+    let Some(entries) = THEMES_DIR.get().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Font styles syntax colors may set to `true` or `false` in a theme file
+const FONT_STYLES: &[(&str, Modifier)] = &[
+    ("bold", Modifier::BOLD),
+    ("dim", Modifier::DIM),
+    ("italic", Modifier::ITALIC),
+    ("underline", Modifier::UNDERLINED),
+    ("strikethrough", Modifier::CROSSED_OUT),
+    ("reversed", Modifier::REVERSED),
+];
+
+/// A theme file: hex colors for `Theme` fields, optionally on top of another theme,
+/// with syntax styles for single languages in `[languages.<name>]` tables. Syntax
+/// colors may also be tables like `{ color = "#565f89", italic = true }`.
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    extends: Option<String>,
+    #[serde(default)]
+    languages: BTreeMap<String, BTreeMap<String, toml::Value>>,
+    #[serde(flatten)]
+    colors: BTreeMap<String, toml::Value>,
+}
+
+/// Load a user theme by name, or `None` if there is no theme file with that name
+pub(super) fn load(name: &str) -> Result<Option<Theme>> {
+    match THEMES_DIR.get() {
+        Some(dir) => load_from(dir, name, 0),
+        None => Ok(None),
+    }
+}
+
+/// Load a theme file outside the themes directory
+pub(super) fn load_file(path: &Path) -> Result<Theme> {
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .with_context(|| format!("Invalid theme file name: {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    load_from(dir, name, 0)?.with_context(|| format!("Theme file not found: {}", path.display()))
+}
+
+fn load_from(dir: &Path, name: &str, depth: usize) -> Result<Option<Theme>> {
+    let path = dir.join(format!("{}.toml", name));
+    if !path.is_file() {
+        return Ok(None);
+    }
+    if depth > MAX_EXTENDS_DEPTH {
+        anyhow::bail!("Theme '{}' extends itself through other themes", name);
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+    let file: ThemeFile = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
+
+    parse(dir, name, file, depth)
+        .with_context(|| format!("Invalid theme file: {}", path.display()))
+        .map(Some)
+}
+
+fn parse(dir: &Path, name: &str, file: ThemeFile, depth: usize) -> Result<Theme> {
+    let mut theme = match file.extends.as_deref() {
+        // A theme file named after a built-in theme can tweak that theme
+        Some(base) if base == name => {
+            Theme::builtin(base).with_context(|| format!("Theme '{}' extends itself", name))?
+        }
+        Some(base) => match load_from(dir, base, depth + 1)? {
+            Some(theme) => theme,
+            // Theme files outside the themes directory may extend user themes too
+            None => {
+                Theme::load(base).with_context(|| format!("Unknown theme to extend: {}", base))?
+            }
+        },
+        None => {
+            let missing: Vec<&str> = Theme::FIELDS
+                .iter()
+                .copied()
+                .filter(|field| !Theme::MARKER_FIELDS.contains(field))
+                .filter(|field| !file.colors.get(*field).is_some_and(has_color))
+                .collect();
+            if !missing.is_empty() {
+                anyhow::bail!(
+                    "Missing colors: {} (set `extends` to inherit them from another theme)",
+                    missing.join(", ")
+                );
+            }
+            Theme::default()
+        }
+    };
+
+    for (key, value) in &file.colors {
+        if !Theme::has_field(key) {
+            anyhow::bail!("Unknown theme color: {}", key);
+        }
+        let style = parse_style(value).with_context(|| format!("Invalid color for {}", key))?;
+        if let Some(color) = style.fg {
+            theme.set_color(key, color);
+        }
+        let font_style = Style { fg: None, ..style };
+        if font_style != Style::new() && !theme.set_font_style(key, font_style) {
+            anyhow::bail!(
+                "Font styles can only be set for syntax_* colors, not {}",
+                key
+            );
+        }
+    }
+
+    // Marker colors the file leaves out follow its own file tree and background colors.
+    // An extended theme's markers are kept unless the file changes the colors they
+    // would be derived from.
+    for field in Theme::MARKER_FIELDS {
+        let sets = |field: &str| file.colors.get(field).is_some_and(has_color);
+        if sets(field) {
+            continue;
+        }
+        if file.extends.is_some() && !Theme::marker_inputs(field).iter().any(|f| sets(f)) {
+            continue;
+        }
+        if let Some(color) = theme.derived_marker_color(field) {
+            theme.set_color(field, color);
+        }
+    }
+
+    for (language, colors) in &file.languages {
+        if !LANGUAGE_NAMES.contains(&language.as_str()) {
+            anyhow::bail!(
+                "Unknown language: {} (expected one of: {})",
+                language,
+                LANGUAGE_NAMES.join(", ")
+            );
+        }
+        for (key, value) in colors {
+            let style = parse_style(value)
+                .with_context(|| format!("Invalid color for {}.{}", language, key))?;
+            if !theme.set_language_style(language, key, style) {
+                anyhow::bail!(
+                    "Unknown syntax color for {}: {} (languages can only set syntax_* colors)",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+    Ok(theme)
+}
+
+/// Whether a theme file entry sets a color, rather than only font styles
+fn has_color(value: &toml::Value) -> bool {
+    value.is_str() || value.get("color").is_some()
+}
+
+/// Parse a hex color, or a table with an optional color and font styles
+fn parse_style(value: &toml::Value) -> Result<Style> {
+    let table = match value {
+        toml::Value::String(hex) => return Ok(Style::new().fg(parse_hex(hex)?)),
+        toml::Value::Table(table) => table,
+        _ => anyhow::bail!(
+            "Expected a hex color or a table like {{ color = \"#565f89\", italic = true }}"
+        ),
+    };
+
+    let mut style = Style::new();
+    for (key, value) in table {
+        if key == "color" {
+            let hex = value.as_str().context("Expected color to be a string")?;
+            style.fg = Some(parse_hex(hex)?);
+            continue;
+        }
+        let (_, modifier) = FONT_STYLES
+            .iter()
+            .find(|(name, _)| name == key)
+            .with_context(|| {
+                let names: Vec<&str> = FONT_STYLES.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "Unknown font style: {} (expected color, {})",
+                    key,
+                    names.join(", ")
+                )
+            })?;
+        style = match value.as_bool() {
+            Some(true) => style.add_modifier(*modifier),
+            Some(false) => style.remove_modifier(*modifier),
+            None => anyhow::bail!("Expected {} to be true or false", key),
+        };
+    }
+    Ok(style)
+}
+
+/// Parse a `#rrggbb` color
+fn parse_hex(value: &str) -> Result<Color> {
+    let hex = value
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .with_context(|| format!("Expected a hex color like \"#1e2236\", got \"{}\"", value))?;
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16);
+    Ok(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::TokenType;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "gitlogue-themes-{}-{}",
+                name,
+                std::process::id()
+            ));
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn write_theme(dir: &Path, name: &str, contents: &str) {
+        fs::write(dir.join(format!("{}.toml", name)), contents).unwrap();
+    }
+
+    #[test]
+    fn test_extends_overrides_only_given_colors() {
+        let dir = TempDir::new("extends");
+        write_theme(
+            dir.path(),
+            "arctic",
+            "extends = \"nord\"\nsyntax_keyword = \"#ff0080\"\n",
+        );
+        write_theme(
+            dir.path(),
+            "arctic-night",
+            "extends = \"arctic\"\nbackground_right = \"#000000\"\n",
+        );
+
+        let theme = load_from(dir.path(), "arctic-night", 0).unwrap().unwrap();
+        let nord = Theme::builtin("nord").unwrap();
+        assert_eq!(theme.syntax_keyword, Color::Rgb(255, 0, 128));
+        assert_eq!(theme.background_right, Color::Rgb(0, 0, 0));
+        assert_eq!(theme.syntax_string, nord.syntax_string);
+
+        // Change markers left out follow the new background rather than the base theme's
+        assert_eq!(
+            Some(theme.editor_line_added_bg),
+            theme.derived_marker_color("editor_line_added_bg")
+        );
+        assert_ne!(theme.editor_line_added_bg, nord.editor_line_added_bg);
+        // but are kept when their inputs are left alone
+        assert_eq!(theme.editor_gutter_added, nord.editor_gutter_added);
+        let arctic = load_from(dir.path(), "arctic", 0).unwrap().unwrap();
+        assert_eq!(arctic.editor_line_added_bg, nord.editor_line_added_bg);
+        assert_eq!(arctic.editor_line_modified_bg, nord.editor_line_modified_bg);
+    }
+
+    #[test]
+    fn test_theme_without_extends_needs_every_color() {
+        let dir = TempDir::new("required");
+        write_theme(dir.path(), "partial", "syntax_keyword = \"#ff0080\"\n");
+        let error = format!("{:#}", load_from(dir.path(), "partial", 0).unwrap_err());
+        assert!(error.contains("Missing colors: background_left"));
+
+        // Change marker colors are derived from the file tree colors when left out
+        let full: String = Theme::FIELDS
+            .iter()
+            .filter(|field| !Theme::MARKER_FIELDS.contains(field))
+            .map(|field| match *field {
+                "file_tree_added" => format!("{} = \"#30c030\"\n", field),
+                _ => format!("{} = \"#102030\"\n", field),
+            })
+            .collect();
+        write_theme(dir.path(), "full", &full);
+        let theme = load_from(dir.path(), "full", 0).unwrap().unwrap();
+        assert_eq!(theme.syntax_label, Color::Rgb(16, 32, 48));
+        assert_eq!(theme.editor_gutter_added, Color::Rgb(48, 192, 48));
+        assert_eq!(theme.editor_gutter_modified, Color::Rgb(16, 32, 48));
+        assert_eq!(theme.editor_line_added_bg, Color::Rgb(19, 51, 47));
+    }
+
+    #[test]
+    fn test_invalid_theme_files_are_reported() {
+        let dir = TempDir::new("invalid");
+        write_theme(
+            dir.path(),
+            "typo",
+            "extends = \"nord\"\nsyntax_keywrd = \"#ff0080\"\n",
+        );
+        write_theme(
+            dir.path(),
+            "short",
+            "extends = \"nord\"\nseparator = \"#fff\"\n",
+        );
+        write_theme(dir.path(), "a", "extends = \"b\"\n");
+        write_theme(dir.path(), "b", "extends = \"a\"\n");
+
+        for (name, message) in [
+            ("typo", "Unknown theme color: syntax_keywrd"),
+            ("short", "Expected a hex color"),
+            ("a", "extends itself"),
+        ] {
+            let error = format!("{:#}", load_from(dir.path(), name, 0).unwrap_err());
+            assert!(error.contains(message), "{}: {}", name, error);
+        }
+
+        // A file named after a built-in theme tweaks it
+        write_theme(
+            dir.path(),
+            "nord",
+            "extends = \"nord\"\nseparator = \"#ffffff\"\n",
+        );
+        let theme = load_from(dir.path(), "nord", 0).unwrap().unwrap();
+        assert_eq!(theme.separator, Color::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn test_finer_and_per_language_syntax_colors() {
+        let dir = TempDir::new("languages");
+        write_theme(
+            dir.path(),
+            "macros",
+            "extends = \"nord\"\nsyntax_macro = \"#ff0000\"\n\n\
+             [languages.rust]\nsyntax_keyword = \"#00ff00\"\nsyntax_macro = \"#0000ff\"\n",
+        );
+
+        let theme = load_from(dir.path(), "macros", 0).unwrap().unwrap();
+        let nord = Theme::builtin("nord").unwrap();
+        let rust = Some("rust");
+        assert_eq!(
+            theme.syntax_style("syntax_macro", None).fg,
+            Some(Color::Rgb(255, 0, 0))
+        );
+        assert_eq!(
+            theme.syntax_style("syntax_macro", rust).fg,
+            Some(Color::Rgb(0, 0, 255))
+        );
+        assert_eq!(
+            theme.syntax_style("syntax_keyword", rust).fg,
+            Some(Color::Rgb(0, 255, 0))
+        );
+        assert_eq!(
+            theme.syntax_style("syntax_keyword", Some("python")).fg,
+            Some(nord.syntax_keyword)
+        );
+
+        write_theme(
+            dir.path(),
+            "cobol",
+            "extends = \"nord\"\n[languages.cobol]\nsyntax_keyword = \"#00ff00\"\n",
+        );
+        write_theme(
+            dir.path(),
+            "ui",
+            "extends = \"nord\"\n[languages.rust]\nseparator = \"#00ff00\"\n",
+        );
+        for (name, message) in [
+            ("cobol", "Unknown language: cobol"),
+            ("ui", "Unknown syntax color for rust: separator"),
+        ] {
+            let error = format!("{:#}", load_from(dir.path(), name, 0).unwrap_err());
+            assert!(error.contains(message), "{}: {}", name, error);
+        }
+    }
+
+    #[test]
+    fn test_syntax_font_styles() {
+        let dir = TempDir::new("styles");
+        write_theme(
+            dir.path(),
+            "styled",
+            "extends = \"nord\"\n\
+             syntax_comment = { italic = true }\n\
+             syntax_comment_doc = { color = \"#ff0000\", bold = true }\n\
+             syntax_keyword = { color = \"#00ff00\", bold = true }\n\n\
+             [languages.python]\nsyntax_keyword = { bold = false, underline = true }\n",
+        );
+
+        let theme = load_from(dir.path(), "styled", 0).unwrap().unwrap();
+        let nord = Theme::builtin("nord").unwrap();
+        assert_eq!(
+            TokenType::Comment.style(&theme, None),
+            Style::new()
+                .fg(nord.syntax_comment)
+                .add_modifier(Modifier::ITALIC)
+        );
+        // Finer categories add to the broader category's font styles
+        assert_eq!(
+            TokenType::CommentDoc.style(&theme, None),
+            Style::new()
+                .fg(Color::Rgb(255, 0, 0))
+                .add_modifier(Modifier::ITALIC | Modifier::BOLD)
+        );
+        assert_eq!(
+            TokenType::Attribute.style(&theme, Some("python")),
+            Style::new()
+                .fg(Color::Rgb(0, 255, 0))
+                .remove_modifier(Modifier::BOLD)
+                .add_modifier(Modifier::UNDERLINED)
+        );
+
+        for (contents, message) in [
+            (
+                "syntax_keyword = { blink = true }",
+                "Unknown font style: blink",
+            ),
+            (
+                "syntax_keyword = { bold = \"yes\" }",
+                "Expected bold to be true or false",
+            ),
+            (
+                "separator = { bold = true }",
+                "only be set for syntax_* colors",
+            ),
+            ("syntax_keyword = 3", "Expected a hex color or a table"),
+        ] {
+            write_theme(
+                dir.path(),
+                "invalid",
+                &format!("extends = \"nord\"\n{}\n", contents),
+            );
+            let error = format!("{:#}", load_from(dir.path(), "invalid", 0).unwrap_err());
+            assert!(error.contains(message), "{}: {}", contents, error);
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Built-in timing presets
+pub const PRESETS: &[&str] = &["default", "calm", "demo", "hyper"];
+
+/// Pause durations for each animation phase, as multipliers of the typing speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    /// Cursor movement between lines (base speed)
+    pub cursor_move: f64,
+    /// After deleting a line
+    pub delete_line: f64,
+    /// After inserting a line
+    pub insert_line: f64,
+    /// Between hunks
+    pub hunk: f64,
+    /// After git checkout command
+    pub checkout: f64,
+    /// After git checkout output
+    pub checkout_output: f64,
+    /// Before opening first file
+    pub open_file_first: f64,
+    /// Before opening subsequent files
+    pub open_file: f64,
+    /// After the open file dialog appears
+    pub open_dialog: f64,
+    /// After open command
+    pub open_cmd: f64,
+    /// After switching file
+    pub file_switch: f64,
+    /// Before git add
+    pub git_add: f64,
+    /// After git add command
+    pub git_add_cmd: f64,
+    /// After git commit command
+    pub git_commit: f64,
+    /// After commit output
+    pub commit_output: f64,
+    /// After git push command
+    pub git_push: f64,
+    /// Between push output lines
+    pub push_output: f64,
+    /// After final push output
+    pub push_final: f64,
+    /// Idle gap between commits
+    pub idle: f64,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            cursor_move: 0.5,
+            delete_line: 10.0,
+            insert_line: 6.7,
+            hunk: 50.0,
+            checkout: 16.7,
+            checkout_output: 33.3,
+            open_file_first: 33.3,
+            open_file: 50.0,
+            open_dialog: 5.0,
+            open_cmd: 16.7,
+            file_switch: 26.7,
+            git_add: 33.3,
+            git_add_cmd: 16.7,
+            git_commit: 26.7,
+            commit_output: 33.3,
+            git_push: 16.7,
+            push_output: 10.0,
+            push_final: 66.7,
+            idle: 100.0,
+        }
+    }
+}
+
+impl Timing {
+    /// Get a built-in timing preset by name
+    pub fn preset(name: &str) -> Result<Self> {
+        match name {
+            "default" => Ok(Self::default()),
+            // Unhurried pacing with long breaks between commits (lobby screens, ambient displays)
+            "calm" => Ok(Self {
+                idle: 400.0,
+                ..Self::default().scaled(2.0)
+            }),
+            // Snappy terminal phases and no idle time between commits (live demos)
+            "demo" => Ok(Self {
+                idle: 0.0,
+                ..Self::default().scaled(0.5)
+            }),
+            // Barely pause at all
+            "hyper" => Ok(Self {
+                idle: 0.0,
+                ..Self::default().scaled(0.1)
+            }),
+            _ => anyhow::bail!(
+                "Unknown timing preset: {}\nAvailable presets: {}",
+                name,
+                PRESETS.join(", ")
+            ),
+        }
+    }
+
+    /// Resolve a `--timing` argument, which is either a preset name or a path to a timing profile
+    pub fn from_arg(value: &str) -> Result<Self> {
+        if PRESETS.contains(&value) {
+            return Self::preset(value);
+        }
+
+        let path = Path::new(value);
+        if path.is_file() {
+            return Self::from_file(path);
+        }
+
+        Self::preset(value)
+    }
+
+    /// Load a timing profile file (same format as the `[timing]` config table)
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read timing profile: {}", path.display()))?;
+        let config: TimingConfig = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse timing profile: {}", path.display()))?;
+        config.resolve()
+    }
+
+    /// Scale every pause except the idle gap between commits
+    fn scaled(self, factor: f64) -> Self {
+        Self {
+            cursor_move: self.cursor_move * factor,
+            delete_line: self.delete_line * factor,
+            insert_line: self.insert_line * factor,
+            hunk: self.hunk * factor,
+            checkout: self.checkout * factor,
+            checkout_output: self.checkout_output * factor,
+            open_file_first: self.open_file_first * factor,
+            open_file: self.open_file * factor,
+            open_dialog: self.open_dialog * factor,
+            open_cmd: self.open_cmd * factor,
+            file_switch: self.file_switch * factor,
+            git_add: self.git_add * factor,
+            git_add_cmd: self.git_add_cmd * factor,
+            git_commit: self.git_commit * factor,
+            commit_output: self.commit_output * factor,
+            git_push: self.git_push * factor,
+            push_output: self.push_output * factor,
+            push_final: self.push_final * factor,
+            idle: self.idle,
+        }
+    }
+}
+
+/// The `[timing]` config table: an optional preset plus per-phase overrides
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimingConfig {
+    pub preset: Option<String>,
+    pub cursor_move: Option<f64>,
+    pub delete_line: Option<f64>,
+    pub insert_line: Option<f64>,
+    pub hunk: Option<f64>,
+    pub checkout: Option<f64>,
+    pub checkout_output: Option<f64>,
+    pub open_file_first: Option<f64>,
+    pub open_file: Option<f64>,
+    pub open_dialog: Option<f64>,
+    pub open_cmd: Option<f64>,
+    pub file_switch: Option<f64>,
+    pub git_add: Option<f64>,
+    pub git_add_cmd: Option<f64>,
+    pub git_commit: Option<f64>,
+    pub commit_output: Option<f64>,
+    pub git_push: Option<f64>,
+    pub push_output: Option<f64>,
+    pub push_final: Option<f64>,
+    pub idle: Option<f64>,
+}
+
+impl TimingConfig {
+    /// Build the effective timing: start from the preset, then apply overrides
+    pub fn resolve(&self) -> Result<Timing> {
+        let base = Timing::preset(self.preset.as_deref().unwrap_or("default"))?;
+        let timing = Timing {
+            cursor_move: self.cursor_move.unwrap_or(base.cursor_move),
+            delete_line: self.delete_line.unwrap_or(base.delete_line),
+            insert_line: self.insert_line.unwrap_or(base.insert_line),
+            hunk: self.hunk.unwrap_or(base.hunk),
+            checkout: self.checkout.unwrap_or(base.checkout),
+            checkout_output: self.checkout_output.unwrap_or(base.checkout_output),
+            open_file_first: self.open_file_first.unwrap_or(base.open_file_first),
+            open_file: self.open_file.unwrap_or(base.open_file),
+            open_dialog: self.open_dialog.unwrap_or(base.open_dialog),
+            open_cmd: self.open_cmd.unwrap_or(base.open_cmd),
+            file_switch: self.file_switch.unwrap_or(base.file_switch),
+            git_add: self.git_add.unwrap_or(base.git_add),
+            git_add_cmd: self.git_add_cmd.unwrap_or(base.git_add_cmd),
+            git_commit: self.git_commit.unwrap_or(base.git_commit),
+            commit_output: self.commit_output.unwrap_or(base.commit_output),
+            git_push: self.git_push.unwrap_or(base.git_push),
+            push_output: self.push_output.unwrap_or(base.push_output),
+            push_final: self.push_final.unwrap_or(base.push_final),
+            idle: self.idle.unwrap_or(base.idle),
+        };
+        timing.validate()?;
+        Ok(timing)
+    }
+}
+
+impl Timing {
+    fn validate(&self) -> Result<()> {
+        let values = [
+            ("cursor_move", self.cursor_move),
+            ("delete_line", self.delete_line),
+            ("insert_line", self.insert_line),
+            ("hunk", self.hunk),
+            ("checkout", self.checkout),
+            ("checkout_output", self.checkout_output),
+            ("open_file_first", self.open_file_first),
+            ("open_file", self.open_file),
+            ("open_dialog", self.open_dialog),
+            ("open_cmd", self.open_cmd),
+            ("file_switch", self.file_switch),
+            ("git_add", self.git_add),
+            ("git_add_cmd", self.git_add_cmd),
+            ("git_commit", self.git_commit),
+            ("commit_output", self.commit_output),
+            ("git_push", self.git_push),
+            ("push_output", self.push_output),
+            ("push_final", self.push_final),
+            ("idle", self.idle),
+        ];
+        for (name, value) in values {
+            if !value.is_finite() || value < 0.0 {
+                anyhow::bail!("Invalid timing value for {}: {}", name, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Target wall-clock duration for each commit's animation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DurationTarget {
+    pub target: Option<Duration>,
+    pub min: Option<Duration>,
+    pub max: Option<Duration>,
+}
+
+impl DurationTarget {
+    pub fn validate(&self) -> Result<()> {
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                anyhow::bail!(
+                    "Minimum duration ({:?}) is longer than maximum duration ({:?})",
+                    min,
+                    max
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Factor to multiply the typing speed by so an animation estimated to take
+    /// `estimated_ms` lands on the target, or within the min/max bounds
+    pub fn speed_scale(&self, estimated_ms: f64) -> f64 {
+        if estimated_ms <= 0.0 {
+            return 1.0;
+        }
+
+        let mut desired_ms = self
+            .target
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(estimated_ms);
+        if let Some(min) = self.min {
+            desired_ms = desired_ms.max(min.as_secs_f64() * 1000.0);
+        }
+        if let Some(max) = self.max {
+            desired_ms = desired_ms.min(max.as_secs_f64() * 1000.0);
+        }
+
+        desired_ms / estimated_ms
+    }
+}
+
+/// Parse a duration such as "30s", "1m30s", "500ms", "2h" or "45" (seconds)
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("Duration cannot be empty".to_string());
+    }
+    if let Ok(secs) = s.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).map_err(|_| format!("Invalid duration: {}", s));
+    }
+
+    let mut total = 0.0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let unit_len = rest[number_len..]
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len() - number_len);
+        let number: f64 = rest[..number_len]
+            .parse()
+            .map_err(|_| format!("Invalid duration: {}", s))?;
+        let unit_secs = match &rest[number_len..number_len + unit_len] {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return Err(format!("Invalid duration: {} (use units ms, s, m or h)", s)),
+        };
+        total += number * unit_secs;
+        rest = &rest[number_len + unit_len..];
+    }
+
+    Duration::try_from_secs_f64(total).map_err(|_| format!("Invalid duration: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_apply_on_top_of_preset() {
+        let config: TimingConfig = toml::from_str("preset = \"calm\"\nhunk = 5.0\n").unwrap();
+        let timing = config.resolve().unwrap();
+        let calm = Timing::preset("calm").unwrap();
+
+        assert_eq!(timing.hunk, 5.0);
+        assert_eq!(timing.checkout, calm.checkout);
+        assert_eq!(timing.idle, 400.0);
+    }
+
+    #[test]
+    fn test_invalid_timing_is_rejected() {
+        assert!(Timing::preset("sleepy").is_err());
+        let config: TimingConfig = toml::from_str("idle = -1.0\n").unwrap();
+        assert!(config.resolve().is_err());
+        // A misspelled phase would otherwise be ignored
+        assert!(toml::from_str::<TimingConfig>("hnuk = 5.0\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("1m30s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+        assert!(parse_duration("30x").is_err());
+        assert!(parse_duration("s").is_err());
+    }
+
+    #[test]
+    fn test_speed_scale_clamps_to_bounds() {
+        let target = DurationTarget {
+            target: None,
+            min: Some(Duration::from_secs(10)),
+            max: Some(Duration::from_secs(60)),
+        };
+        assert_eq!(target.speed_scale(5_000.0), 2.0);
+        assert_eq!(target.speed_scale(30_000.0), 1.0);
+        assert_eq!(target.speed_scale(120_000.0), 0.5);
+
+        let fixed = DurationTarget {
+            target: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        assert_eq!(fixed.speed_scale(60_000.0), 0.5);
+    }
+}
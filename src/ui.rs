@@ -3,15 +3,19 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    clipboard::CopyToClipboard,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    backend::{CrosstermBackend, TestBackend},
+    layout::{Constraint, Direction, Layout, Position, Rect, Size},
     style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph},
@@ -19,12 +23,18 @@ use ratatui::{
 };
 use unicode_width::UnicodeWidthStr;
 
-use crate::animation::{AnimationEngine, SpeedRule};
+use crate::animation::{AnimationEngine, DeletedFileMode, EditOrder, SpeedRule};
+use crate::appearance::ThemeSwitcher;
 use crate::git::{CommitMetadata, DiffMode, GitRepository};
-use crate::panes::{EditorPane, FileTreePane, StatusBarPane, TerminalPane};
+use crate::panes::{DiffView, EditorPane, FileTreePane, StatusBarPane, TerminalPane};
+use crate::record::FrameSink;
 use crate::theme::Theme;
+use crate::timing::{DurationTarget, Timing};
 use crate::PlaybackOrder;
 
+/// How long a theme change cross-fades from the old colors to the new ones
+const THEME_FADE: Duration = Duration::from_millis(400);
+
 #[derive(Debug, Clone, PartialEq)]
 enum UIState {
     Playing,
@@ -32,6 +42,147 @@ enum UIState {
     Finished,
 }
 
+/// Sizes and visibility of the panes around the editor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaneLayout {
+    /// Width of the left column (file tree and commit info) in percent
+    pub sidebar_width: u16,
+    /// Height of the file tree within the left column in percent
+    pub file_tree_height: u16,
+    /// Height of the editor within the right column in percent
+    pub editor_height: u16,
+    pub file_tree: bool,
+    pub commit_info: bool,
+    pub terminal: bool,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            sidebar_width: 30,
+            file_tree_height: 80,
+            editor_height: 80,
+            file_tree: true,
+            commit_info: true,
+            terminal: true,
+        }
+    }
+}
+
+/// Screen areas of the visible panes and the separators between them
+struct PaneAreas {
+    file_tree: Option<Rect>,
+    left_separator: Option<Rect>,
+    commit_info: Option<Rect>,
+    editor: Rect,
+    right_separator: Option<Rect>,
+    terminal: Option<Rect>,
+}
+
+impl PaneLayout {
+    fn areas(&self, area: Rect) -> PaneAreas {
+        // Split horizontally: left column | right column
+        let (left, right) = if self.file_tree || self.commit_info {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(self.sidebar_width), // File tree + commit info
+                    Constraint::Fill(1),                        // Editor + terminal
+                ])
+                .split(area);
+            (Some(columns[0]), columns[1])
+        } else {
+            (None, area)
+        };
+
+        // Split left column vertically: file tree | separator | commit info
+        let (file_tree, left_separator, commit_info) = match left {
+            Some(left) if self.file_tree && self.commit_info => {
+                let rows = Self::split_rows(left, self.file_tree_height);
+                (Some(rows[0]), Some(rows[1]), Some(rows[2]))
+            }
+            Some(left) if self.file_tree => (Some(left), None, None),
+            Some(left) => (None, None, Some(left)),
+            None => (None, None, None),
+        };
+
+        // Split right column vertically: editor | separator | terminal
+        let (editor, right_separator, terminal) = if self.terminal {
+            let rows = Self::split_rows(right, self.editor_height);
+            (rows[0], Some(rows[1]), Some(rows[2]))
+        } else {
+            (right, None, None)
+        };
+
+        PaneAreas {
+            file_tree,
+            left_separator,
+            commit_info,
+            editor,
+            right_separator,
+            terminal,
+        }
+    }
+
+    fn split_rows(area: Rect, top_percent: u16) -> std::rc::Rc<[Rect]> {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(top_percent),
+                Constraint::Length(1), // Horizontal separator
+                Constraint::Fill(1),
+            ])
+            .split(area)
+    }
+}
+
+/// Themes cycled through with the arrow keys while a sample commit plays
+pub struct ThemePicker {
+    themes: Vec<(String, Theme)>,
+    index: usize,
+    sample: CommitMetadata,
+    chosen: Option<String>,
+}
+
+impl ThemePicker {
+    /// Starts at `current`, which must be one of `themes`
+    pub fn new(
+        themes: Vec<(String, Theme)>,
+        current: &str,
+        sample: CommitMetadata,
+    ) -> Result<Self> {
+        if themes.is_empty() {
+            anyhow::bail!("No themes could be loaded");
+        }
+        let index = themes
+            .iter()
+            .position(|(name, _)| name == current)
+            .with_context(|| format!("Theme '{}' could not be loaded", current))?;
+        Ok(Self {
+            themes,
+            index,
+            sample,
+            chosen: None,
+        })
+    }
+
+    fn current(&self) -> &(String, Theme) {
+        &self.themes[self.index]
+    }
+
+    fn cycle(&mut self, step: isize) {
+        let len = self.themes.len() as isize;
+        self.index = (self.index as isize + step).rem_euclid(len) as usize;
+    }
+}
+
+/// A theme change in progress
+struct ThemeFade {
+    from: Theme,
+    to: Theme,
+    started: Instant,
+}
+
 /// Main UI controller for the gitlogue terminal interface.
 pub struct UI<'a> {
     state: UIState,
@@ -49,6 +200,11 @@ pub struct UI<'a> {
     commit_spec: Option<String>,
     is_range_mode: bool,
     diff_mode: Option<DiffMode>,
+    diff_view: DiffView,
+    layout: PaneLayout,
+    picker: Option<ThemePicker>,
+    theme_switcher: Option<ThemeSwitcher>,
+    fade: Option<ThemeFade>,
 }
 
 impl<'a> UI<'a> {
@@ -74,9 +230,9 @@ impl<'a> UI<'a> {
             state: UIState::Playing,
             speed_ms,
             file_tree: FileTreePane::new(),
-            editor: EditorPane,
+            editor: EditorPane::default(),
             terminal: TerminalPane,
-            status_bar: StatusBarPane,
+            status_bar: StatusBarPane::default(),
             engine,
             repo,
             should_exit,
@@ -86,14 +242,115 @@ impl<'a> UI<'a> {
             commit_spec,
             is_range_mode,
             diff_mode: None,
+            diff_view: DiffView::default(),
+            layout: PaneLayout::default(),
+            picker: None,
+            theme_switcher: None,
+            fade: None,
         }
     }
 
+    /// Enables editor auto-pair and auto-indent simulation.
+    pub fn set_smart_edit(&mut self, enabled: bool) {
+        self.engine.set_smart_edit(enabled);
+    }
+
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.engine.set_timing(timing);
+    }
+
+    pub fn set_duration_target(&mut self, target: DurationTarget) {
+        self.engine.set_duration_target(target);
+    }
+
+    pub fn set_deleted_file_mode(&mut self, mode: DeletedFileMode) {
+        self.engine.set_deleted_file_mode(mode);
+    }
+
+    pub fn set_animate_renames(&mut self, enabled: bool) {
+        self.engine.set_animate_renames(enabled);
+    }
+
+    pub fn set_edit_order(&mut self, order: EditOrder) {
+        self.engine.set_edit_order(order);
+    }
+
+    /// Shows the old file beside the buffer being typed.
+    pub fn set_diff_view(&mut self, view: DiffView) {
+        self.diff_view = view;
+        self.editor.set_view(view);
+    }
+
+    /// Marks lines added or modified by the commit in the editor's gutter.
+    pub fn set_change_markers(&mut self, enabled: bool) {
+        self.editor.set_change_markers(enabled);
+    }
+
+    /// Resizes or hides the panes around the editor.
+    pub fn set_layout(&mut self, layout: PaneLayout) {
+        self.layout = layout;
+    }
+
     /// Sets the diff mode for working tree diff playback.
     pub fn set_diff_mode(&mut self, mode: Option<DiffMode>) {
         self.diff_mode = mode;
     }
 
+    /// Loops the picker's sample commit, switching themes with the arrow keys.
+    pub fn set_theme_picker(&mut self, picker: ThemePicker) {
+        if picker.themes.is_empty() {
+            return;
+        }
+        self.theme = picker.current().1.clone();
+        self.load_commit(picker.sample.clone());
+        self.picker = Some(picker);
+    }
+
+    /// Switches between a light and a dark theme between commits.
+    pub fn set_theme_switcher(&mut self, switcher: Option<ThemeSwitcher>) {
+        self.theme_switcher = switcher;
+    }
+
+    /// Theme selected with Enter in the theme picker.
+    pub fn chosen_theme(&self) -> Option<&str> {
+        self.picker.as_ref()?.chosen.as_deref()
+    }
+
+    fn cycle_theme(&mut self, step: isize) {
+        if let Some(picker) = &mut self.picker {
+            picker.cycle(step);
+            let theme = picker.current().1.clone();
+            self.change_theme(theme);
+        }
+    }
+
+    /// Cross-fades from the colors on screen to a new theme.
+    fn change_theme(&mut self, theme: Theme) {
+        self.fade = Some(ThemeFade {
+            from: self.theme.clone(),
+            to: theme,
+            started: Instant::now(),
+        });
+        self.step_fade();
+    }
+
+    /// Moves a theme cross-fade on to the current frame, returning whether the colors changed.
+    fn step_fade(&mut self) -> bool {
+        let Some(fade) = self.fade.take() else {
+            return false;
+        };
+        let amount = fade.started.elapsed().as_secs_f32() / THEME_FADE.as_secs_f32();
+        if amount >= 1.0 {
+            self.theme = fade.to;
+        } else {
+            self.theme = fade.from.mix(&fade.to, amount);
+            self.fade = Some(fade);
+        }
+        // Rebuild the file tree lines in the new colors
+        self.file_tree = FileTreePane::new();
+        true
+    }
+
     fn setup_signal_handler(should_exit: Arc<AtomicBool>) {
         ctrlc::set_handler(move || {
             // Restore terminal state before exiting
@@ -132,6 +389,50 @@ impl<'a> UI<'a> {
         result
     }
 
+    /// Renders the loaded commit off-screen, starting at `start_ms` on the recording's clock.
+    /// Frames are timed by the engine's step timing, so recording runs faster than real time.
+    /// Returns the time at which the commit's animation ends.
+    pub fn record(
+        &mut self,
+        sink: &mut dyn FrameSink,
+        width: u16,
+        height: u16,
+        start_ms: u64,
+    ) -> Result<u64> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        self.update_viewport(Size::new(width, height));
+
+        let mut end_ms = start_ms;
+        for entry in self.engine.timeline() {
+            self.engine.run_next_step();
+            let frame = terminal.draw(|f| self.render(f))?;
+            sink.frame(start_ms + entry.at_ms, frame.buffer)?;
+            end_ms = start_ms + entry.at_ms + entry.duration_ms;
+        }
+        Ok(end_ms)
+    }
+
+    /// Idle time between commits, as an idle multiplier of the typing speed
+    pub fn idle_ms(&self) -> u64 {
+        (self.speed_ms as f64 * self.engine.timing().idle) as u64
+    }
+
+    fn update_viewport(&mut self, size: Size) {
+        let editor = self
+            .layout
+            .areas(Rect::from((Position::ORIGIN, size)))
+            .editor;
+        // Subtract the editor's top and bottom padding
+        let viewport_height = editor.height.saturating_sub(2) as usize;
+        // Halved when the old file is shown beside the buffer
+        let mut content_width = editor.width as usize;
+        if self.diff_view == DiffView::Split {
+            content_width = content_width.saturating_sub(1) / 2;
+        }
+        self.engine.set_viewport_height(viewport_height);
+        self.engine.set_content_width(content_width);
+    }
+
     fn cleanup(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
         disable_raw_mode()?;
         execute!(
@@ -151,16 +452,11 @@ impl<'a> UI<'a> {
             }
 
             // Update viewport dimensions for scroll calculation
-            let size = terminal.size()?;
-            // Editor area: 70% (right column) × 80% (editor pane) = 56% of total height
-            let viewport_height = (size.height as f32 * 0.70 * 0.80) as usize;
-            // Editor width: 70% (right column)
-            let content_width = (size.width as f32 * 0.70) as usize;
-            self.engine.set_viewport_height(viewport_height);
-            self.engine.set_content_width(content_width);
+            self.update_viewport(terminal.size()?);
 
-            // Tick the animation engine
-            let needs_redraw = self.engine.tick();
+            // Tick the animation engine and any theme cross-fade
+            let fading = self.step_fade();
+            let needs_redraw = self.engine.tick() || fading;
 
             if needs_redraw {
                 terminal.draw(|f| self.render(f))?;
@@ -168,16 +464,33 @@ impl<'a> UI<'a> {
 
             // Poll for keyboard events at frame rate
             if event::poll(std::time::Duration::from_millis(8))? {
-                if let Event::Key(key) = event::read()? {
-                    match key.code {
+                match event::read()? {
+                    Event::Key(key) => match key.code {
                         KeyCode::Esc | KeyCode::Char('q') => {
                             self.state = UIState::Finished;
                         }
                         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                             self.state = UIState::Finished;
                         }
+                        KeyCode::Char(' ') => {
+                            self.engine.toggle_pause();
+                        }
+                        KeyCode::Left | KeyCode::Right if self.picker.is_some() => {
+                            self.cycle_theme(if key.code == KeyCode::Left { -1 } else { 1 });
+                            terminal.draw(|f| self.render(f))?;
+                        }
+                        KeyCode::Enter => {
+                            if let Some(picker) = &mut self.picker {
+                                picker.chosen = Some(picker.current().0.clone());
+                                self.state = UIState::Finished;
+                            }
+                        }
                         _ => {}
+                    },
+                    Event::Mouse(mouse) if self.handle_mouse(mouse, terminal.size()?)? => {
+                        terminal.draw(|f| self.render(f))?;
                     }
+                    _ => {}
                 }
             }
 
@@ -185,12 +498,11 @@ impl<'a> UI<'a> {
             match self.state {
                 UIState::Playing => {
                     if self.engine.is_finished() {
-                        if self.repo.is_some() {
+                        if self.repo.is_some() || self.picker.is_some() {
                             // Schedule next commit
-                            // Wait time proportional to speed (100x the typing speed)
+                            // Wait time proportional to speed (idle multiplier of the typing speed)
                             self.state = UIState::WaitingForNext {
-                                resume_at: Instant::now()
-                                    + Duration::from_millis(self.speed_ms * 100),
+                                resume_at: Instant::now() + Duration::from_millis(self.idle_ms()),
                             };
                         } else {
                             // Single commit mode without loop - quit
@@ -199,9 +511,17 @@ impl<'a> UI<'a> {
                     }
                 }
                 UIState::WaitingForNext { resume_at } => {
-                    if Instant::now() >= resume_at {
-                        // Handle diff mode looping
-                        if let Some(diff_mode) = self.diff_mode {
+                    if !self.engine.is_paused_by_viewer() && Instant::now() >= resume_at {
+                        // Follow changes of the terminal background or the time of day
+                        if let Some(theme) = self.theme_switcher.as_mut().and_then(|s| s.update()) {
+                            self.change_theme(theme);
+                        }
+
+                        // Replay the theme picker's sample
+                        if let Some(picker) = &self.picker {
+                            let sample = picker.sample.clone();
+                            self.load_commit(sample);
+                        } else if let Some(diff_mode) = self.diff_mode {
                             if let Some(repo) = self.repo {
                                 // Refresh the working tree diff
                                 match repo.get_working_tree_diff(diff_mode) {
@@ -281,44 +601,61 @@ impl<'a> UI<'a> {
         Ok(())
     }
 
-    fn render(&mut self, f: &mut Frame) {
-        let size = f.area();
-
-        // Split horizontally: left column | right column
-        let main_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(30), // Left column (file tree + commit info)
-                Constraint::Percentage(70), // Right column (editor + terminal)
-            ])
-            .margin(0)
-            .spacing(0)
-            .split(size);
-
-        // Split left column vertically: file tree | separator | commit info
-        let left_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(80), // File tree
-                Constraint::Length(1),      // Horizontal separator
-                Constraint::Percentage(20), // Commit info
-            ])
-            .margin(0)
-            .spacing(0)
-            .split(main_layout[0]);
+    /// Handles a click or wheel event, returning whether the screen needs a redraw.
+    fn handle_mouse(&mut self, mouse: MouseEvent, size: Size) -> Result<bool> {
+        let areas = self.layout.areas(Rect::from((Position::ORIGIN, size)));
+        let position = Position::new(mouse.column, mouse.row);
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // Jump to a file clicked in the file tree
+                if let Some(area) = areas.file_tree.filter(|area| area.contains(position)) {
+                    let Some(file_index) = self.file_tree.file_at(area, mouse.row, &self.theme)
+                    else {
+                        return Ok(false);
+                    };
+                    if self.engine.jump_to_file(file_index) {
+                        self.state = UIState::Playing;
+                        return Ok(true);
+                    }
+                }
 
-        // Split right column vertically: editor | separator | terminal
-        let right_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(80), // Editor
-                Constraint::Length(1),      // Horizontal separator
-                Constraint::Percentage(20), // Terminal
-            ])
-            .margin(0)
-            .spacing(0)
-            .split(main_layout[1]);
+                // Copy the commit hash clicked in the commit info
+                if areas.commit_info.is_some()
+                    && self.status_bar.hash_contains(mouse.column, mouse.row)
+                {
+                    if let Some(metadata) = self
+                        .engine
+                        .current_metadata()
+                        .filter(|m| m.hash != "working-tree")
+                    {
+                        execute!(
+                            io::stdout(),
+                            CopyToClipboard::to_clipboard_from(metadata.hash.as_str())
+                        )?;
+                    }
+                }
+                Ok(false)
+            }
+            // Scroll the editor freely while paused
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                if self.engine.is_paused_by_viewer() && areas.editor.contains(position) =>
+            {
+                let lines = if mouse.kind == MouseEventKind::ScrollUp {
+                    -3
+                } else {
+                    3
+                };
+                self.engine.scroll_editor(lines);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
 
+    fn render(&mut self, f: &mut Frame) {
+        let size = f.area();
+        let areas = self.layout.areas(size);
         let separator_color = self.theme.separator;
 
         // Update file tree data if needed
@@ -331,40 +668,61 @@ impl<'a> UI<'a> {
         }
 
         // Render file tree
-        self.file_tree.render(f, left_layout[0], &self.theme);
+        if let Some(area) = areas.file_tree {
+            self.file_tree.render(f, area, &self.theme);
+        }
 
         // Render horizontal separator between file tree and commit info (left column)
-        let left_sep = Paragraph::new(Line::from("─".repeat(left_layout[1].width as usize))).style(
-            Style::default()
-                .fg(separator_color)
-                .bg(self.theme.background_left),
-        );
-        f.render_widget(left_sep, left_layout[1]);
+        if let Some(area) = areas.left_separator {
+            let left_sep = Paragraph::new(Line::from("─".repeat(area.width as usize))).style(
+                Style::default()
+                    .fg(separator_color)
+                    .bg(self.theme.background_left),
+            );
+            f.render_widget(left_sep, area);
+        }
 
         // Render commit info
-        self.status_bar.render(
-            f,
-            left_layout[2],
-            self.engine.current_metadata(),
-            &self.theme,
-        );
+        if let Some(area) = areas.commit_info {
+            self.status_bar
+                .render(f, area, self.engine.current_metadata(), &self.theme);
+        }
 
         // Render editor
         self.editor
-            .render(f, right_layout[0], &self.engine, &self.theme);
+            .render(f, areas.editor, &self.engine, &self.theme);
 
         // Render horizontal separator between editor and terminal (right column)
-        let right_sep = Paragraph::new(Line::from("─".repeat(right_layout[1].width as usize)))
-            .style(
+        if let Some(area) = areas.right_separator {
+            let right_sep = Paragraph::new(Line::from("─".repeat(area.width as usize))).style(
                 Style::default()
                     .fg(separator_color)
                     .bg(self.theme.background_right),
             );
-        f.render_widget(right_sep, right_layout[1]);
+            f.render_widget(right_sep, area);
+        }
 
         // Render terminal
-        self.terminal
-            .render(f, right_layout[2], &self.engine, &self.theme);
+        if let Some(area) = areas.terminal {
+            self.terminal.render(f, area, &self.engine, &self.theme);
+        }
+
+        // Render theme picker bar
+        if let Some(picker) = &self.picker {
+            let (name, _) = picker.current();
+            let text = format!(
+                "◀ {} ({}/{}) ▶   Enter: save as default   q: quit",
+                name,
+                picker.index + 1,
+                picker.themes.len()
+            );
+            let bar = Paragraph::new(Line::from(text).centered()).style(
+                Theme::highlight(self.theme.editor_cursor_line_bg)
+                    .fg(self.theme.file_tree_current_file_fg),
+            );
+            let area = Rect::new(0, size.height.saturating_sub(1), size.width, 1);
+            f.render_widget(bar, area.intersection(size));
+        }
 
         // Render dialog if present
         if let Some(ref title) = self.engine.dialog_title {
@@ -414,3 +772,33 @@ impl<'a> UI<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hidden_panes_give_their_space_to_the_editor() {
+        let area = Rect::new(0, 0, 100, 50);
+
+        let areas = PaneLayout::default().areas(area);
+        assert_eq!(areas.editor, Rect::new(30, 0, 70, 40));
+        assert_eq!(areas.terminal, Some(Rect::new(30, 41, 70, 9)));
+        assert_eq!(areas.file_tree, Some(Rect::new(0, 0, 30, 40)));
+
+        let no_sidebar = PaneLayout {
+            file_tree: false,
+            commit_info: false,
+            ..PaneLayout::default()
+        };
+        assert_eq!(no_sidebar.areas(area).editor, Rect::new(0, 0, 100, 40));
+
+        let editor_only = PaneLayout {
+            terminal: false,
+            ..no_sidebar
+        };
+        let areas = editor_only.areas(area);
+        assert_eq!(areas.editor, area);
+        assert!(areas.terminal.is_none() && areas.right_separator.is_none());
+    }
+}
@@ -1,12 +1,14 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::Line,
     widgets::{Block, Padding, Widget},
 };
 use unicode_width::UnicodeWidthStr;
 
+use crate::theme;
+
 /// A paragraph widget that wraps at character boundaries and supports line selection
 pub struct SelectableParagraph<'a> {
     lines: Vec<Line<'a>>,
@@ -64,16 +66,18 @@ impl<'a> SelectableParagraph<'a> {
         self
     }
 
-    fn apply_opacity(&self, foreground: Color, opacity: f32, background: Color) -> Color {
-        match (foreground, background) {
-            (Color::Rgb(fr, fg, fb), Color::Rgb(br, bg, bb)) => {
-                // Blend foreground and background: result = fg * opacity + bg * (1 - opacity)
-                let r = (fr as f32 * opacity + br as f32 * (1.0 - opacity)) as u8;
-                let g = (fg as f32 * opacity + bg as f32 * (1.0 - opacity)) as u8;
-                let b = (fb as f32 * opacity + bb as f32 * (1.0 - opacity)) as u8;
-                Color::Rgb(r, g, b)
+    /// Fade the foreground toward the background, or use the terminal's dim attribute
+    /// for far lines when the colors can't be blended
+    fn apply_opacity(&self, style: Style, opacity: f32, background: Color) -> Style {
+        let Some(foreground) = style.fg else {
+            return style;
+        };
+        match theme::blend(foreground, background, opacity) {
+            Some(color) => style.fg(color),
+            None if opacity < (1.0 + self.dim_min_opacity) / 2.0 => {
+                style.add_modifier(Modifier::DIM)
             }
-            _ => foreground, // For non-RGB colors, return as-is
+            None => style,
         }
     }
 
@@ -131,7 +135,7 @@ impl<'a> SelectableParagraph<'a> {
                     }
 
                     // Start new line
-                    wrapped_lines.push(Line::from(current_line_spans.clone()));
+                    wrapped_lines.push(Line::from(current_line_spans.clone()).style(line.style));
                     current_line_spans.clear();
                     current_width = 0;
                     current_span_style = None;
@@ -167,57 +171,39 @@ impl<'a> SelectableParagraph<'a> {
 
         // Add the last line if it has content
         if !current_line_spans.is_empty() {
-            wrapped_lines.push(Line::from(current_line_spans));
+            wrapped_lines.push(Line::from(current_line_spans).style(line.style));
         }
 
         if wrapped_lines.is_empty() {
-            vec![Line::from(vec![])]
+            vec![Line::from(vec![]).style(line.style)]
         } else {
             wrapped_lines
         }
     }
-}
 
-impl Widget for SelectableParagraph<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    /// Area left for the lines inside the block and vertical padding
+    fn content_area(&self, area: Rect) -> Rect {
         let area = match self.block {
-            Some(ref block) => {
-                let inner = block.inner(area);
-                block.clone().render(area, buf);
-                inner
-            }
+            Some(ref block) => block.inner(area),
             None => area,
         };
-
-        if area.width == 0 || area.height == 0 {
-            return;
-        }
-
-        // Apply padding
-        let inner_area = Rect {
+        Rect {
             x: area.x,
             y: area.y.saturating_add(self.padding.top),
             width: area.width,
             height: area
                 .height
                 .saturating_sub(self.padding.top + self.padding.bottom),
-        };
-
-        if inner_area.width == 0 || inner_area.height == 0 {
-            return;
         }
+    }
 
+    /// Wrap all lines and track which wrapped line corresponds to which original line,
+    /// whether it is the first wrapped line and whether wrapping occurred
+    fn wrapped_lines(&self, inner_area: Rect) -> Vec<(usize, Line<'a>, bool, bool)> {
         // For wrapping: first line uses full width minus left padding (no right padding when wrapping)
         let first_line_width = inner_area.width.saturating_sub(self.padding.left) as usize;
         let continuation_width = inner_area.width as usize;
-        let no_wrap_content_width = inner_area
-            .width
-            .saturating_sub(self.padding.left + self.padding.right)
-            as usize;
-        let height = inner_area.height as usize;
 
-        // Wrap all lines and track which wrapped line corresponds to which original line
-        // Also track if this is the first wrapped line and if wrapping occurred
         let mut wrapped_lines_with_indices = Vec::new();
         for (original_idx, line) in self.lines.iter().enumerate() {
             let wrapped = Self::wrap_line(line, first_line_width, continuation_width);
@@ -232,30 +218,73 @@ impl Widget for SelectableParagraph<'_> {
                 ));
             }
         }
+        wrapped_lines_with_indices
+    }
 
-        // Calculate scroll offset to keep selected line centered
-        let scroll_offset = if let Some(selected_idx) = self.selected_line {
-            // Find the first display line of the selected original line
-            let selected_display_line = wrapped_lines_with_indices
-                .iter()
-                .position(|(orig_idx, _, _, _)| *orig_idx == selected_idx)
-                .unwrap_or(0);
+    /// Calculate scroll offset to keep selected line centered
+    fn scroll_offset(
+        &self,
+        wrapped_lines: &[(usize, Line<'a>, bool, bool)],
+        height: usize,
+    ) -> usize {
+        let Some(selected_idx) = self.selected_line else {
+            return 0;
+        };
 
-            let total_lines = wrapped_lines_with_indices.len();
+        // Find the first display line of the selected original line
+        let selected_display_line = wrapped_lines
+            .iter()
+            .position(|(orig_idx, _, _, _)| *orig_idx == selected_idx)
+            .unwrap_or(0);
 
-            if total_lines <= height {
-                // All lines fit, no scrolling needed
-                0
-            } else {
-                // Keep selected line in the middle of viewport
-                let preferred_position = height / 2;
-                let offset = selected_display_line.saturating_sub(preferred_position);
-                let max_offset = total_lines.saturating_sub(height);
-                offset.min(max_offset)
-            }
-        } else {
+        let total_lines = wrapped_lines.len();
+
+        if total_lines <= height {
+            // All lines fit, no scrolling needed
             0
-        };
+        } else {
+            // Keep selected line in the middle of viewport
+            let preferred_position = height / 2;
+            let offset = selected_display_line.saturating_sub(preferred_position);
+            let max_offset = total_lines.saturating_sub(height);
+            offset.min(max_offset)
+        }
+    }
+
+    /// Index of the line shown at screen row `y` when rendered into `area`
+    pub fn line_at(&self, area: Rect, y: u16) -> Option<usize> {
+        let inner_area = self.content_area(area);
+        if y < inner_area.y || y >= inner_area.bottom() {
+            return None;
+        }
+        let wrapped_lines = self.wrapped_lines(inner_area);
+        let scroll_offset = self.scroll_offset(&wrapped_lines, inner_area.height as usize);
+        wrapped_lines
+            .get(scroll_offset + (y - inner_area.y) as usize)
+            .map(|(original_idx, _, _, _)| *original_idx)
+    }
+}
+
+impl Widget for SelectableParagraph<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if let Some(ref block) = self.block {
+            block.clone().render(area, buf);
+        }
+
+        let inner_area = self.content_area(area);
+        if inner_area.width == 0 || inner_area.height == 0 {
+            return;
+        }
+
+        let continuation_width = inner_area.width as usize;
+        let no_wrap_content_width = inner_area
+            .width
+            .saturating_sub(self.padding.left + self.padding.right)
+            as usize;
+        let height = inner_area.height as usize;
+
+        let wrapped_lines_with_indices = self.wrapped_lines(inner_area);
+        let scroll_offset = self.scroll_offset(&wrapped_lines_with_indices, height);
 
         let visible_lines: Vec<_> = wrapped_lines_with_indices
             .into_iter()
@@ -268,18 +297,13 @@ impl Widget for SelectableParagraph<'_> {
             let is_selected = self.selected_line == Some(*original_idx);
             let dim_opacity = self.calculate_dim_opacity(*original_idx);
 
-            let bg_color = if is_selected {
-                self.selected_style.bg
-            } else {
-                self.background_style.bg
-            }
-            .unwrap_or(Color::Reset);
-
+            // A line's own background (e.g. a changed line in the editor) shows unless selected
             let fill_style = if is_selected {
                 self.selected_style
             } else {
-                self.background_style
+                self.background_style.patch(line.style)
             };
+            let bg_color = fill_style.bg.unwrap_or(Color::Reset);
 
             if y >= height {
                 continue;
@@ -303,22 +327,24 @@ impl Widget for SelectableParagraph<'_> {
                 // Render content after left padding
                 let mut x_pos = 0;
                 for span in &line.spans {
-                    let mut style = span.style;
-
-                    // Apply selected style, but preserve span's own bg/fg (child elements take priority)
-                    if is_selected {
-                        if span.style.bg.is_none() {
-                            style.bg = self.selected_style.bg;
-                        }
-                        if span.style.fg.is_none() {
-                            style.fg = self.selected_style.fg;
-                        }
+                    // Apply line background and selected style, but preserve span's own
+                    // bg/fg and modifiers (child elements take priority)
+                    let mut style = if is_selected {
+                        Style::default()
+                            .add_modifier(self.selected_style.add_modifier)
+                            .patch(span.style)
+                    } else {
+                        span.style
+                    };
+                    if span.style.bg.is_none() {
+                        style.bg = fill_style.bg;
+                    }
+                    if is_selected && span.style.fg.is_none() {
+                        style.fg = self.selected_style.fg;
                     }
 
                     // Apply dim to foreground color
-                    if let Some(fg) = style.fg {
-                        style = style.fg(self.apply_opacity(fg, dim_opacity, bg_color));
-                    }
+                    style = self.apply_opacity(style, dim_opacity, bg_color);
 
                     buf.set_string(
                         inner_area.x + self.padding.left + x_pos as u16,
@@ -364,22 +390,24 @@ impl Widget for SelectableParagraph<'_> {
                 // Render content after left padding, use remaining width
                 let mut x_pos = 0;
                 for span in &line.spans {
-                    let mut style = span.style;
-
-                    // Apply selected style, but preserve span's own bg/fg (child elements take priority)
-                    if is_selected {
-                        if span.style.bg.is_none() {
-                            style.bg = self.selected_style.bg;
-                        }
-                        if span.style.fg.is_none() {
-                            style.fg = self.selected_style.fg;
-                        }
+                    // Apply line background and selected style, but preserve span's own
+                    // bg/fg and modifiers (child elements take priority)
+                    let mut style = if is_selected {
+                        Style::default()
+                            .add_modifier(self.selected_style.add_modifier)
+                            .patch(span.style)
+                    } else {
+                        span.style
+                    };
+                    if span.style.bg.is_none() {
+                        style.bg = fill_style.bg;
+                    }
+                    if is_selected && span.style.fg.is_none() {
+                        style.fg = self.selected_style.fg;
                     }
 
                     // Apply dim to foreground color
-                    if let Some(fg) = style.fg {
-                        style = style.fg(self.apply_opacity(fg, dim_opacity, bg_color));
-                    }
+                    style = self.apply_opacity(style, dim_opacity, bg_color);
 
                     buf.set_string(
                         inner_area.x + self.padding.left + x_pos as u16,
@@ -405,22 +433,24 @@ impl Widget for SelectableParagraph<'_> {
                 let mut x_pos = 0;
 
                 for span in &line.spans {
-                    let mut style = span.style;
-
-                    // Apply selected style, but preserve span's own bg/fg (child elements take priority)
-                    if is_selected {
-                        if span.style.bg.is_none() {
-                            style.bg = self.selected_style.bg;
-                        }
-                        if span.style.fg.is_none() {
-                            style.fg = self.selected_style.fg;
-                        }
+                    // Apply line background and selected style, but preserve span's own
+                    // bg/fg and modifiers (child elements take priority)
+                    let mut style = if is_selected {
+                        Style::default()
+                            .add_modifier(self.selected_style.add_modifier)
+                            .patch(span.style)
+                    } else {
+                        span.style
+                    };
+                    if span.style.bg.is_none() {
+                        style.bg = fill_style.bg;
+                    }
+                    if is_selected && span.style.fg.is_none() {
+                        style.fg = self.selected_style.fg;
                     }
 
                     // Apply dim to foreground color
-                    if let Some(fg) = style.fg {
-                        style = style.fg(self.apply_opacity(fg, dim_opacity, bg_color));
-                    }
+                    style = self.apply_opacity(style, dim_opacity, bg_color);
 
                     buf.set_string(
                         inner_area.x + x_pos as u16,